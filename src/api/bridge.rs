@@ -1,8 +1,15 @@
 use rocket::serde::json::{Json, Value};
 use rocket::State;
+use std::str::FromStr;
 use rocket::serde::json::serde_json::json;
-use crate::models::AppState;
+use crate::models::bridge::BridgeEventType;
+use crate::models::numeric::DepositNonce;
+use crate::models::transfer::TransferStatus;
 use crate::repositories::bridge as bridge_repo;
+use crate::repositories::reorg as reorg_repo;
+use crate::repositories::connection as connection_repo;
+use crate::repositories::transfer as transfer_repo;
+use crate::repositories::reconciliation as reconciliation_repo;
 
 #[rocket::get("/bridge/events?<event_type>&<network>&<limit>&<page>")]
 pub async fn get_bridge_events(
@@ -17,6 +24,14 @@ pub async fn get_bridge_events(
     let page_val = page.unwrap_or(1).max(1) as i64;
     let offset = (page_val - 1) * limit_val;
     
+    let event_type = match event_type.as_deref().map(BridgeEventType::from_str).transpose() {
+        Ok(event_type) => event_type,
+        Err(e) => return Json(json!({
+            "error": "Invalid event type",
+            "details": e.to_string()
+        })),
+    };
+
     // Get events from repository
     match bridge_repo::get_bridge_events(&state.db, event_type, network, Some(limit_val), offset).await {
         Ok(events) => {
@@ -34,7 +49,9 @@ pub async fn get_bridge_events(
                         "amount": format!("{} tokens", event.amount),
                         "nonce": event.nonce,
                         "block_number": event.block_number,
-                        "tx_hash": event.tx_hash
+                        "tx_hash": event.tx_hash,
+                        "log_index": event.log_index,
+                        "status": event.status
                     });
                     
                     if let Some(from) = &event.from_address {
@@ -53,4 +70,153 @@ pub async fn get_bridge_events(
             }))
         }
     }
-} 
\ No newline at end of file
+}
+
+#[rocket::get("/bridge/reorgs?<network>&<limit>&<page>")]
+pub async fn get_chain_reorgs(
+    network: Option<String>,
+    limit: Option<u64>,
+    page: Option<u64>,
+    state: &State<crate::models::AppState>
+) -> Json<Value> {
+    // Default values
+    let limit_val = limit.unwrap_or(10).min(100) as i64;
+    let page_val = page.unwrap_or(1).max(1) as i64;
+    let offset = (page_val - 1) * limit_val;
+
+    match reorg_repo::get_reorgs(&state.db, network, Some(limit_val), offset).await {
+        Ok(reorgs) => {
+            Json(json!({
+                "total": reorgs.len(),
+                "page": page_val,
+                "limit": limit_val,
+                "reorgs": reorgs
+            }))
+        },
+        Err(e) => {
+            eprintln!("Error fetching chain reorgs: {:?}", e);
+            Json(json!({
+                "error": "Failed to fetch chain reorgs",
+                "details": format!("{:?}", e)
+            }))
+        }
+    }
+}
+
+#[rocket::get("/bridge/connections")]
+pub async fn get_indexer_connections(state: &State<crate::models::AppState>) -> Json<Value> {
+    match connection_repo::get_connections(&state.db).await {
+        Ok(connections) => Json(json!({ "connections": connections })),
+        Err(e) => {
+            eprintln!("Error fetching indexer connections: {:?}", e);
+            Json(json!({
+                "error": "Failed to fetch indexer connections",
+                "details": format!("{:?}", e)
+            }))
+        }
+    }
+}
+
+#[rocket::get("/bridge/transfers?<network>&<status>&<limit>&<page>")]
+pub async fn get_bridge_transfers(
+    network: Option<String>,
+    status: Option<String>,
+    limit: Option<u64>,
+    page: Option<u64>,
+    state: &State<crate::models::AppState>
+) -> Json<Value> {
+    // Default values
+    let limit_val = limit.unwrap_or(10).min(100) as i64;
+    let page_val = page.unwrap_or(1).max(1) as i64;
+    let offset = (page_val - 1) * limit_val;
+
+    let status = match status.as_deref().map(TransferStatus::from_str).transpose() {
+        Ok(status) => status,
+        Err(e) => return Json(json!({
+            "error": "Invalid transfer status",
+            "details": e.to_string()
+        })),
+    };
+
+    match transfer_repo::get_transfers(&state.db, network, status, Some(limit_val), offset).await {
+        Ok(transfers) => {
+            Json(json!({
+                "total": transfers.len(),
+                "page": page_val,
+                "limit": limit_val,
+                "transfers": transfers
+            }))
+        },
+        Err(e) => {
+            eprintln!("Error fetching bridge transfers: {:?}", e);
+            Json(json!({
+                "error": "Failed to fetch bridge transfers",
+                "details": format!("{:?}", e)
+            }))
+        }
+    }
+}
+
+#[rocket::get("/bridge/transfers/<network>/<nonce>")]
+pub async fn get_bridge_transfer(
+    network: &str,
+    nonce: &str,
+    state: &State<crate::models::AppState>
+) -> Json<Value> {
+    // Nonces are uint256, wider than any integer rocket parses
+    let nonce = match DepositNonce::from_str(nonce) {
+        Ok(nonce) => nonce,
+        Err(e) => return Json(json!({
+            "error": "Invalid nonce",
+            "details": e.to_string()
+        })),
+    };
+
+    match transfer_repo::get_transfer(&state.db, network, nonce).await {
+        Ok(Some(transfer)) => Json(json!({ "transfer": transfer })),
+        Ok(None) => Json(json!({
+            "error": format!("No {} deposit with nonce {}", network, nonce)
+        })),
+        Err(e) => {
+            eprintln!("Error fetching bridge transfer: {:?}", e);
+            Json(json!({
+                "error": "Failed to fetch bridge transfer",
+                "details": format!("{:?}", e)
+            }))
+        }
+    }
+}
+
+#[rocket::get("/bridge/discrepancies?<kind>&<network>&<resolved>&<limit>&<page>")]
+pub async fn get_discrepancies(
+    kind: Option<String>,
+    network: Option<String>,
+    resolved: Option<bool>,
+    limit: Option<u64>,
+    page: Option<u64>,
+    state: &State<crate::models::AppState>
+) -> Json<Value> {
+    // Default values
+    let limit_val = limit.unwrap_or(10).min(100) as i64;
+    let page_val = page.unwrap_or(1).max(1) as i64;
+    let offset = (page_val - 1) * limit_val;
+
+    let resolved = resolved.unwrap_or(false);
+    match reconciliation_repo::get_discrepancies(&state.db, kind, network, resolved, Some(limit_val), offset).await {
+        Ok(discrepancies) => {
+            Json(json!({
+                "total": discrepancies.len(),
+                "page": page_val,
+                "limit": limit_val,
+                "discrepancies": discrepancies
+            }))
+        },
+        Err(e) => {
+            eprintln!("Error fetching discrepancies: {:?}", e);
+            Json(json!({
+                "error": "Failed to fetch discrepancies",
+                "details": format!("{:?}", e)
+            }))
+        }
+    }
+}
@@ -1,14 +1,27 @@
 use dotenv::dotenv;
-use lobster::services::bridge_indexer;
+use lobster::services::{bridge_indexer, reconciler, relayer};
+use lobster::utils::config::{self, BridgeConfig};
 use clap::Parser;
 use eyre::Result;
 
 #[derive(Parser, Debug, Clone)]
 #[clap(author, version, about = "Bridge Event Indexer")]
 struct Args {
+    /// Days to go back in history (default 0, real-time only)
+    #[clap(short, long)]
+    days: Option<u64>,
+
+    /// Start block (overrides days)
+    #[clap(short, long)]
+    start_block: Option<u64>,
+
     /// Batch size for event processing
     #[clap(short, long, default_value = "100")]
     batch_size: u64,
+
+    /// Config file describing the chains and routes
+    #[clap(long, default_value = config::DEFAULT_CONFIG_PATH)]
+    config: String,
 }
 
 #[tokio::main]
@@ -18,11 +31,30 @@ async fn main() -> Result<()> {
     // Parse command line args
     let args = Args::parse();
     
+    // Load the chains and routes
+    let bridge_config = BridgeConfig::load(&args.config)?;
+    let chains = bridge_config.chains.clone();
+
     // Create indexer config
     let config = bridge_indexer::BridgeIndexerConfig {
         batch_size: args.batch_size,
+        days: args.days,
+        start_block: args.start_block,
     };
     
+    // Relay confirmed deposits and reconcile transfers alongside the indexer
+    let reconciler_config = bridge_config.clone();
+    tokio::spawn(async move {
+        if let Err(e) = reconciler::start_reconciler(reconciler_config).await {
+            eprintln!("Reconciler error: {:?}", e);
+        }
+    });
+    tokio::spawn(async move {
+        if let Err(e) = relayer::start_relayer(bridge_config).await {
+            eprintln!("Relayer error: {:?}", e);
+        }
+    });
+
     // Start bridge indexer
-    bridge_indexer::start_bridge_indexer().await
+    bridge_indexer::start_bridge_indexer(chains, config).await
 } 
\ No newline at end of file
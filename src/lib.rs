@@ -5,11 +5,21 @@ pub mod models;
 pub mod services;
 pub mod repositories;
 pub mod utils;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use eyre::Result;
 use rocket::{Build, Rocket};
-use api::bridge::get_bridge_events;
+use api::bridge::{
+    get_bridge_events, get_bridge_transfer, get_bridge_transfers, get_chain_reorgs, get_discrepancies,
+    get_indexer_connections,
+};
 use tokio::task;
+use rocket::response::content::RawHtml;
+
+// Standard route
+#[get("/")]
+fn index() -> RawHtml<&'static str> {
+    RawHtml(include_str!("../static/index.html"))
+}
 
 #[derive(Parser, Debug, Clone)]
 #[clap(author, version, about = "Lobster - Bridge Event Indexer + API")]
@@ -29,27 +39,88 @@ pub struct AppArgs {
     /// Skip starting the indexer
     #[clap(long)]
     pub api_only: bool,
+
+    /// Config file describing the chains and routes
+    #[clap(long, default_value = utils::config::DEFAULT_CONFIG_PATH)]
+    pub config: String,
+
+    #[clap(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    /// Run one reconciliation of deposits and distributions, print the open discrepancies and exit
+    Reconcile,
+}
+
+/// Runs one reconciliation and prints its report, returns the number of open discrepancies
+pub async fn run_reconcile(args: &AppArgs) -> Result<usize> {
+    let bridge_config = utils::config::BridgeConfig::load(&args.config)?;
+    let database_url = std::env::var("DATABASE_URL")
+        .expect("DATABASE_URL must be set in .env file");
+    let pool = repositories::init_db(&database_url).await?;
+
+    let report = services::reconciler::reconcile(&bridge_config, &pool).await?;
+    for discrepancy in &report.discrepancies {
+        println!(
+            "{} {} nonce {} {}: {}",
+            discrepancy.kind,
+            discrepancy.network,
+            discrepancy.nonce,
+            discrepancy.tx_hash.as_deref().unwrap_or("-"),
+            discrepancy.details
+        );
+    }
+    println!("{} open discrepancies, {} resolved", report.discrepancies.len(), report.resolved);
+
+    Ok(report.discrepancies.len())
 }
 
 /// Initializes the application with the given arguments
 pub async fn start_app(args: AppArgs) -> Result<Rocket<Build>> {
     use std::env;
-    use rocket::response::content::RawHtml;
-    use services::bridge_indexer;
+    use services::{bridge_indexer, reconciler, relayer};
 
     println!("Starting application...");
-    
+
+    // Initialize database before the background tasks, which expect its tables to exist
+    let database_url = env::var("DATABASE_URL")
+        .expect("DATABASE_URL must be set in .env file");
+    let pool = repositories::init_db(&database_url).await?;
 
     // Start the indexer in a background task unless --api-only flag is given
     if !args.api_only {
+        let bridge_config = utils::config::BridgeConfig::load(&args.config)?;
+        let chains = bridge_config.chains.clone();
+
+        let config = bridge_indexer::BridgeIndexerConfig {
+            batch_size: args.batch_size,
+            days: args.days,
+            start_block: args.start_block,
+        };
+
         task::spawn(async move {
-            match bridge_indexer::start_bridge_indexer().await {
+            match bridge_indexer::start_bridge_indexer(chains, config).await {
                 Ok(_) => println!("Bridge indexer completed successfully"),
                 Err(e) => eprintln!("Bridge indexer error: {:?}", e),
             }
         });
         
-        println!("Bridge indexer started in background");
+        let reconciler_config = bridge_config.clone();
+        task::spawn(async move {
+            if let Err(e) = relayer::start_relayer(bridge_config).await {
+                eprintln!("Relayer error: {:?}", e);
+            }
+        });
+
+        task::spawn(async move {
+            if let Err(e) = reconciler::start_reconciler(reconciler_config).await {
+                eprintln!("Reconciler error: {:?}", e);
+            }
+        });
+
+        println!("Bridge indexer, relayer and reconciler started in background");
     } else {
         println!("Running in API-only mode (indexer disabled)");
     }
@@ -57,25 +128,19 @@ pub async fn start_app(args: AppArgs) -> Result<Rocket<Build>> {
     println!("Starting web server...");
     println!("API available at http://localhost:8000");
     
-    // Initialize database
-    let database_url = env::var("DATABASE_URL")
-        .expect("DATABASE_URL must be set in .env file");
-    let pool = repositories::init_db(&database_url).await?;
-
     // AppState
     let app_state = models::AppState { db: pool.clone() };
 
-    // Standard route
-    #[get("/")]
-    fn index() -> RawHtml<&'static str> {
-        RawHtml(include_str!("../static/index.html"))
-    }
-
     // Build Rocket instance
     let rocket = rocket::build()
         .mount("/", routes![index])
         .mount("/eth", routes![
-            get_bridge_events
+            get_bridge_events,
+            get_chain_reorgs,
+            get_indexer_connections,
+            get_bridge_transfers,
+            get_bridge_transfer,
+            get_discrepancies
         ])
         .manage(app_state)
         .configure(rocket::Config::figment().merge(("json.pretty", true)));
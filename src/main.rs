@@ -7,7 +7,7 @@ pub mod utils;
 
 use dotenv::dotenv;
 use eyre::Result;
-use lobster::{start_app, AppArgs};
+use lobster::{run_reconcile, start_app, AppArgs, Command};
 use clap::Parser;
 
 #[tokio::main]
@@ -18,6 +18,14 @@ async fn main() -> Result<()> {
     // Load environment variables
     dotenv().ok();
     
+    if let Some(Command::Reconcile) = args.command {
+        // Exit with a failure status when discrepancies are open, for cron jobs and alerting
+        if run_reconcile(&args).await? > 0 {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     // Start the application
     let rocket = start_app(args).await?;
     
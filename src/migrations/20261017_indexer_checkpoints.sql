@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS indexer_checkpoints (
+    network TEXT NOT NULL,
+    contract_address TEXT NOT NULL,
+    block_number BIGINT NOT NULL,
+    log_index BIGINT,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (network, contract_address)
+);
@@ -0,0 +1,27 @@
+-- Block hash of every stored event, and whether a reorg removed it from the canonical chain
+ALTER TABLE bridge_events ADD COLUMN IF NOT EXISTS block_hash TEXT;
+ALTER TABLE bridge_events ADD COLUMN IF NOT EXISTS orphaned BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE INDEX IF NOT EXISTS bridge_events_block_hash_idx ON bridge_events(block_hash);
+
+-- Recent block hashes seen by the indexer, used to detect parent-hash mismatches
+CREATE TABLE IF NOT EXISTS indexed_blocks (
+    network TEXT NOT NULL,
+    block_number BIGINT NOT NULL,
+    block_hash TEXT NOT NULL,
+    parent_hash TEXT NOT NULL,
+    PRIMARY KEY (network, block_number)
+);
+
+-- History of detected chain reorganizations
+CREATE TABLE IF NOT EXISTS chain_reorgs (
+    id SERIAL PRIMARY KEY,
+    network TEXT NOT NULL,
+    cause TEXT NOT NULL,
+    fork_block BIGINT NOT NULL,
+    depth BIGINT NOT NULL,
+    orphaned_events BIGINT NOT NULL,
+    detected_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS chain_reorgs_network_idx ON chain_reorgs(network);
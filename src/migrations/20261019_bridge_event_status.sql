@@ -0,0 +1,5 @@
+-- Deposits wait as pending until they are deep enough to be relayed.
+-- Existing rows were already handled, so they start out confirmed.
+ALTER TABLE bridge_events ADD COLUMN IF NOT EXISTS status TEXT NOT NULL DEFAULT 'Confirmed';
+
+CREATE INDEX IF NOT EXISTS bridge_events_status_idx ON bridge_events(status);
@@ -0,0 +1,5 @@
+-- Position of the log in its block, so every log is stored at most once per network
+ALTER TABLE bridge_events ADD COLUMN IF NOT EXISTS log_index BIGINT;
+
+CREATE UNIQUE INDEX IF NOT EXISTS bridge_events_network_tx_hash_log_index_key
+    ON bridge_events(network, tx_hash, log_index);
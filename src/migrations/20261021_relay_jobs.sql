@@ -0,0 +1,18 @@
+-- One relay per deposit: the row is claimed before signing so a deposit is never relayed twice
+CREATE TABLE IF NOT EXISTS relay_jobs (
+    id SERIAL PRIMARY KEY,
+    source_network TEXT NOT NULL,
+    deposit_nonce BIGINT NOT NULL,
+    deposit_tx_hash TEXT,
+    target_network TEXT NOT NULL,
+    status TEXT NOT NULL,
+    tx_hash TEXT,
+    distribution_tx_hash TEXT,
+    error TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (source_network, deposit_nonce)
+);
+
+CREATE INDEX IF NOT EXISTS relay_jobs_status_idx ON relay_jobs(status);
+CREATE INDEX IF NOT EXISTS relay_jobs_target_idx ON relay_jobs(target_network, deposit_nonce);
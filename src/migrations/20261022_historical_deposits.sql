@@ -0,0 +1,9 @@
+-- The relayer now relays every confirmed deposit without a relay job.
+-- Deposits handled before relay jobs existed must not be relayed again.
+UPDATE bridge_events e SET status = 'Historical'
+WHERE e.event_type IN ('Deposit', 'SwapDeposit')
+AND e.status = 'Confirmed'
+AND NOT EXISTS (
+    SELECT 1 FROM relay_jobs j
+    WHERE j.source_network = e.network AND j.deposit_nonce = e.nonce
+);
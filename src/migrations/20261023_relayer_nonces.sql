@@ -0,0 +1,8 @@
+-- Next nonce the relayer will use for its account on each network
+CREATE TABLE IF NOT EXISTS relayer_nonces (
+    network TEXT NOT NULL,
+    address TEXT NOT NULL,
+    next_nonce BIGINT NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (network, address)
+);
@@ -0,0 +1,23 @@
+-- Every distribution transaction broadcast for a relay job, including fee-bumped replacements
+CREATE TABLE IF NOT EXISTS relay_transactions (
+    id SERIAL PRIMARY KEY,
+    job_id INTEGER NOT NULL REFERENCES relay_jobs(id),
+    network TEXT NOT NULL,
+    attempt INTEGER NOT NULL,
+    tx_hash TEXT NOT NULL UNIQUE,
+    nonce BIGINT NOT NULL,
+    to_address TEXT NOT NULL,
+    call_data TEXT NOT NULL,
+    gas BIGINT NOT NULL,
+    gas_price TEXT,
+    max_fee_per_gas TEXT,
+    max_priority_fee_per_gas TEXT,
+    status TEXT NOT NULL,
+    block_number BIGINT,
+    gas_used BIGINT,
+    sent_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    mined_at TIMESTAMP WITH TIME ZONE,
+    UNIQUE (job_id, attempt)
+);
+
+CREATE INDEX IF NOT EXISTS relay_transactions_status_idx ON relay_transactions(status);
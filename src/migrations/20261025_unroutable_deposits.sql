@@ -0,0 +1,2 @@
+-- Deposits of tokens without a route are recorded as Unroutable jobs, which have no target chain
+ALTER TABLE relay_jobs ALTER COLUMN target_network DROP NOT NULL;
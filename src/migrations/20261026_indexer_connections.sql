@@ -0,0 +1,10 @@
+-- Live connection state of the indexer of every network, updated by its supervisor
+CREATE TABLE IF NOT EXISTS indexer_connections (
+    network TEXT PRIMARY KEY,
+    state TEXT NOT NULL,
+    -- Consecutive sessions that ended without staying connected
+    failures INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    connected_at TIMESTAMP WITH TIME ZONE,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
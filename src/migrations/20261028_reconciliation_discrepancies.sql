@@ -0,0 +1,78 @@
+-- The reconciler compares the recipient and the amount of a distribution with its deposit,
+-- and only expects confirmed deposits to be paid out
+DROP VIEW IF EXISTS bridge_transfers;
+CREATE VIEW bridge_transfers AS
+SELECT
+    d.network AS source_network,
+    d.nonce,
+    d.event_type AS deposit_type,
+    d.token_address AS source_token,
+    d.from_address AS sender,
+    d.to_address AS recipient,
+    d.amount,
+    d.tx_hash AS deposit_tx_hash,
+    d.block_number AS deposit_block,
+    d.created_at AS deposited_at,
+    j.target_network,
+    j.status AS relay_status,
+    j.tx_hash AS relay_tx_hash,
+    j.error AS relay_error,
+    x.tx_hash AS distribution_tx_hash,
+    x.block_number AS distribution_block,
+    x.token_address AS target_token,
+    COALESCE(x.target_amount, x.amount) AS distributed_amount,
+    x.created_at AS distributed_at,
+    CASE
+        WHEN x.id IS NOT NULL OR j.status = 'Completed' THEN 'Completed'
+        WHEN j.status IN ('Failed', 'Unroutable') THEN 'Failed'
+        WHEN d.status = 'Historical' THEN 'Historical'
+        -- Confirmed or relayed long ago and still not paid out
+        WHEN d.status = 'Confirmed' AND d.created_at < CURRENT_TIMESTAMP - INTERVAL '30 minutes' THEN 'Stuck'
+        WHEN j.status = 'Sent' THEN 'Relayed'
+        ELSE 'Pending'
+    END AS status,
+    EXTRACT(EPOCH FROM x.created_at - d.created_at)::BIGINT AS latency_seconds,
+    x.to_address AS distribution_recipient,
+    x.amount AS distribution_source_amount,
+    d.status AS deposit_status
+FROM bridge_events d
+LEFT JOIN relay_jobs j ON j.source_network = d.network AND j.deposit_nonce = d.nonce
+LEFT JOIN LATERAL (
+    SELECT e.id, e.tx_hash, e.block_number, e.token_address, e.to_address, e.amount, e.target_amount, e.created_at
+    FROM bridge_events e
+    WHERE e.network = j.target_network
+    AND e.nonce = d.nonce
+    AND e.event_type IN ('Distribution', 'SwapDistribution')
+    AND e.orphaned = FALSE
+    AND (
+        e.tx_hash = j.distribution_tx_hash
+        OR (
+            j.distribution_tx_hash IS NULL
+            AND NOT EXISTS (
+                SELECT 1 FROM relay_jobs o
+                WHERE o.target_network = j.target_network AND o.deposit_nonce = j.deposit_nonce AND o.id <> j.id
+            )
+        )
+    )
+    ORDER BY e.block_number, e.id
+    LIMIT 1
+) x ON TRUE
+WHERE d.event_type IN ('Deposit', 'SwapDeposit')
+AND d.orphaned = FALSE;
+
+-- Deposits never paid out, distributions without a deposit and pairs that do not agree.
+-- Rows found again by a reconciliation run stay open, the others are resolved.
+CREATE TABLE IF NOT EXISTS reconciliation_discrepancies (
+    id SERIAL PRIMARY KEY,
+    kind TEXT NOT NULL,
+    network TEXT NOT NULL,
+    nonce BIGINT NOT NULL,
+    tx_hash TEXT,
+    details TEXT NOT NULL,
+    first_seen_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_seen_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    resolved_at TIMESTAMP WITH TIME ZONE,
+    UNIQUE (kind, network, nonce)
+);
+
+CREATE INDEX IF NOT EXISTS reconciliation_discrepancies_open_idx ON reconciliation_discrepancies(resolved_at);
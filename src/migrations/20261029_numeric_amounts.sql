@@ -0,0 +1,65 @@
+-- Amounts become numbers so they can be summed, filtered and sorted in SQL.
+-- NUMERIC(78,0) holds every uint256. The transfers view depends on the columns and is rebuilt.
+DROP VIEW IF EXISTS bridge_transfers;
+
+ALTER TABLE bridge_events ALTER COLUMN amount TYPE NUMERIC(78,0) USING trim(amount::TEXT)::NUMERIC(78,0);
+ALTER TABLE bridge_events ALTER COLUMN target_amount TYPE NUMERIC(78,0) USING NULLIF(trim(target_amount::TEXT), '')::NUMERIC(78,0);
+
+CREATE VIEW bridge_transfers AS
+SELECT
+    d.network AS source_network,
+    d.nonce,
+    d.event_type AS deposit_type,
+    d.token_address AS source_token,
+    d.from_address AS sender,
+    d.to_address AS recipient,
+    d.amount,
+    d.tx_hash AS deposit_tx_hash,
+    d.block_number AS deposit_block,
+    d.created_at AS deposited_at,
+    j.target_network,
+    j.status AS relay_status,
+    j.tx_hash AS relay_tx_hash,
+    j.error AS relay_error,
+    x.tx_hash AS distribution_tx_hash,
+    x.block_number AS distribution_block,
+    x.token_address AS target_token,
+    COALESCE(x.target_amount, x.amount) AS distributed_amount,
+    x.created_at AS distributed_at,
+    CASE
+        WHEN x.id IS NOT NULL OR j.status = 'Completed' THEN 'Completed'
+        WHEN j.status IN ('Failed', 'Unroutable') THEN 'Failed'
+        WHEN d.status = 'Historical' THEN 'Historical'
+        -- Confirmed or relayed long ago and still not paid out
+        WHEN d.status = 'Confirmed' AND d.created_at < CURRENT_TIMESTAMP - INTERVAL '30 minutes' THEN 'Stuck'
+        WHEN j.status = 'Sent' THEN 'Relayed'
+        ELSE 'Pending'
+    END AS status,
+    EXTRACT(EPOCH FROM x.created_at - d.created_at)::BIGINT AS latency_seconds,
+    x.to_address AS distribution_recipient,
+    x.amount AS distribution_source_amount,
+    d.status AS deposit_status
+FROM bridge_events d
+LEFT JOIN relay_jobs j ON j.source_network = d.network AND j.deposit_nonce = d.nonce
+LEFT JOIN LATERAL (
+    SELECT e.id, e.tx_hash, e.block_number, e.token_address, e.to_address, e.amount, e.target_amount, e.created_at
+    FROM bridge_events e
+    WHERE e.network = j.target_network
+    AND e.nonce = d.nonce
+    AND e.event_type IN ('Distribution', 'SwapDistribution')
+    AND e.orphaned = FALSE
+    AND (
+        e.tx_hash = j.distribution_tx_hash
+        OR (
+            j.distribution_tx_hash IS NULL
+            AND NOT EXISTS (
+                SELECT 1 FROM relay_jobs o
+                WHERE o.target_network = j.target_network AND o.deposit_nonce = j.deposit_nonce AND o.id <> j.id
+            )
+        )
+    )
+    ORDER BY e.block_number, e.id
+    LIMIT 1
+) x ON TRUE
+WHERE d.event_type IN ('Deposit', 'SwapDeposit')
+AND d.orphaned = FALSE;
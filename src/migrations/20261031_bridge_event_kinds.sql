@@ -0,0 +1,12 @@
+-- Each event type has exactly its own columns set. Rows stored before are not rechecked.
+ALTER TABLE bridge_events DROP CONSTRAINT IF EXISTS bridge_events_kind_check;
+ALTER TABLE bridge_events ADD CONSTRAINT bridge_events_kind_check CHECK (
+    (event_type = 'Deposit'
+        AND from_address IS NOT NULL AND source_token IS NULL AND target_token IS NULL AND target_amount IS NULL)
+    OR (event_type = 'Distribution'
+        AND from_address IS NULL AND source_token IS NULL AND target_token IS NULL AND target_amount IS NULL)
+    OR (event_type = 'SwapDeposit'
+        AND from_address IS NOT NULL AND source_token IS NOT NULL AND target_token IS NOT NULL AND target_amount IS NULL)
+    OR (event_type = 'SwapDistribution'
+        AND from_address IS NULL AND source_token IS NOT NULL AND target_token IS NOT NULL AND target_amount IS NOT NULL)
+) NOT VALID;
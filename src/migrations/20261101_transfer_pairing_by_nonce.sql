@@ -0,0 +1,72 @@
+-- Pair deposits with their distributions by nonce, so deposits without a relay job (historical ones,
+-- ones from before the relayer, or relayed by another operator) are paired too.
+-- Relay jobs only narrow down the target network and disambiguate deposits sharing a nonce.
+CREATE INDEX IF NOT EXISTS bridge_events_nonce_idx ON bridge_events(nonce);
+
+DROP VIEW IF EXISTS bridge_transfers;
+CREATE VIEW bridge_transfers AS
+SELECT
+    d.network AS source_network,
+    d.nonce,
+    d.event_type AS deposit_type,
+    d.token_address AS source_token,
+    d.from_address AS sender,
+    d.to_address AS recipient,
+    d.amount,
+    d.tx_hash AS deposit_tx_hash,
+    d.block_number AS deposit_block,
+    d.created_at AS deposited_at,
+    -- Deposits relayed without a job, e.g. historical ones, take the network they were paid out on
+    COALESCE(j.target_network, x.network) AS target_network,
+    j.status AS relay_status,
+    j.tx_hash AS relay_tx_hash,
+    j.error AS relay_error,
+    x.tx_hash AS distribution_tx_hash,
+    x.block_number AS distribution_block,
+    x.token_address AS target_token,
+    COALESCE(x.target_amount, x.amount) AS distributed_amount,
+    x.created_at AS distributed_at,
+    CASE
+        WHEN x.id IS NOT NULL OR j.status = 'Completed' THEN 'Completed'
+        WHEN j.status IN ('Failed', 'Unroutable') THEN 'Failed'
+        WHEN d.status = 'Historical' THEN 'Historical'
+        -- Confirmed or relayed long ago and still not paid out
+        WHEN d.status = 'Confirmed' AND d.created_at < CURRENT_TIMESTAMP - INTERVAL '30 minutes' THEN 'Stuck'
+        WHEN j.status = 'Sent' THEN 'Relayed'
+        ELSE 'Pending'
+    END AS status,
+    EXTRACT(EPOCH FROM x.created_at - d.created_at)::BIGINT AS latency_seconds,
+    x.to_address AS distribution_recipient,
+    x.amount AS distribution_source_amount,
+    d.status AS deposit_status
+FROM bridge_events d
+LEFT JOIN relay_jobs j ON j.source_network = d.network AND j.deposit_nonce = d.nonce
+LEFT JOIN LATERAL (
+    SELECT e.id, e.network, e.tx_hash, e.block_number, e.token_address, e.to_address, e.amount, e.target_amount,
+        e.created_at
+    FROM bridge_events e
+    WHERE e.nonce = d.nonce
+    AND e.network <> d.network
+    AND (j.target_network IS NULL OR e.network = j.target_network)
+    AND e.event_type IN ('Distribution', 'SwapDistribution')
+    AND e.orphaned = FALSE
+    AND (
+        -- Only a deposit of the same nonce on another chain could have been paid out by the distribution
+        NOT EXISTS (
+            SELECT 1 FROM bridge_events o
+            WHERE o.nonce = d.nonce
+            AND o.id <> d.id
+            AND o.network <> e.network
+            AND o.event_type IN ('Deposit', 'SwapDeposit')
+            AND o.orphaned = FALSE
+        )
+        -- Otherwise the relay job tells which distribution it sent
+        OR e.tx_hash = j.distribution_tx_hash
+        OR e.tx_hash = j.tx_hash
+        OR EXISTS (SELECT 1 FROM relay_transactions t WHERE t.job_id = j.id AND t.tx_hash = e.tx_hash)
+    )
+    ORDER BY e.block_number, e.id
+    LIMIT 1
+) x ON TRUE
+WHERE d.event_type IN ('Deposit', 'SwapDeposit')
+AND d.orphaned = FALSE;
@@ -0,0 +1,3 @@
+-- Head of the chain when a network's history was first backfilled. Deposits up to it are historical,
+-- so a backfill interrupted by a restart resumes from the checkpoint without relaying them.
+ALTER TABLE indexer_checkpoints ADD COLUMN IF NOT EXISTS history_end BIGINT;
@@ -1,145 +1,246 @@
 use serde::{Serialize, Deserialize};
+use std::str::FromStr;
 use web3::types::{Address, U256};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use crate::models::numeric::{DepositNonce, TokenAmount};
+
+/// Kind of a bridge event, stored in the event_type column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum BridgeEventType {
     Deposit,
     Distribution,
+    SwapDeposit,
+    SwapDistribution,
 }
 
-impl ToString for BridgeEventType {
-    fn to_string(&self) -> String {
+impl BridgeEventType {
+    /// Whether events of this kind are made on the source chain and relayed
+    pub fn is_deposit(&self) -> bool {
+        matches!(self, BridgeEventType::Deposit | BridgeEventType::SwapDeposit)
+    }
+}
+
+impl std::fmt::Display for BridgeEventType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            BridgeEventType::Deposit => "Deposit".to_string(),
-            BridgeEventType::Distribution => "Distribution".to_string(),
+            BridgeEventType::Deposit => write!(f, "Deposit"),
+            BridgeEventType::Distribution => write!(f, "Distribution"),
+            BridgeEventType::SwapDeposit => write!(f, "SwapDeposit"),
+            BridgeEventType::SwapDistribution => write!(f, "SwapDistribution"),
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BridgeEvent {
-    pub id: Option<i32>,
-    pub event_type: String,
-    pub network: String,
-    pub token_address: String,
-    pub from_address: Option<String>,
-    pub to_address: String,
-    pub amount: String,
-    pub nonce: i64,
-    pub block_number: Option<i64>,
-    pub tx_hash: Option<String>,
-    pub source_token: Option<String>,
-    pub target_token: Option<String>,
-    pub target_amount: Option<String>,
+impl FromStr for BridgeEventType {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> eyre::Result<Self> {
+        match s {
+            "Deposit" => Ok(BridgeEventType::Deposit),
+            "Distribution" => Ok(BridgeEventType::Distribution),
+            "SwapDeposit" => Ok(BridgeEventType::SwapDeposit),
+            "SwapDistribution" => Ok(BridgeEventType::SwapDistribution),
+            _ => Err(eyre::eyre!("Unknown bridge event type {}", s)),
+        }
+    }
 }
 
-impl BridgeEvent {
-    pub fn new_deposit(
-        network: &str,
+text_column!(BridgeEventType);
+
+/// A bridge event with exactly the fields its kind carries
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BridgeEventKind {
+    Deposit {
         token: Address,
         from: Address,
         to: Address,
         amount: U256,
-        nonce: U256,
-        block_number: Option<u64>,
-        tx_hash: Option<String>,
-    ) -> eyre::Result<Self> {
-        Ok(Self {
-            id: None,
-            event_type: BridgeEventType::Deposit.to_string(),
-            network: network.to_string(),
-            token_address: format!("{:?}", token),
-            from_address: Some(format!("{:?}", from)),
-            to_address: format!("{:?}", to),
-            amount: amount.to_string(),
-            nonce: nonce.as_u64() as i64,
-            block_number: block_number.map(|bn| bn as i64),
-            tx_hash,
-            source_token: None,
-            target_token: None,
-            target_amount: None,
-        })
-    }
-
-    pub fn new_distribution(
-        network: &str,
+    },
+    Distribution {
         token: Address,
         to: Address,
         amount: U256,
-        nonce: U256,
-        block_number: Option<u64>,
-        tx_hash: Option<String>,
-    ) -> eyre::Result<Self> {
-        Ok(Self {
-            id: None,
-            event_type: BridgeEventType::Distribution.to_string(),
-            network: network.to_string(),
-            token_address: format!("{:?}", token),
-            from_address: None,
-            to_address: format!("{:?}", to),
-            amount: amount.to_string(),
-            nonce: nonce.as_u64() as i64,
-            block_number: block_number.map(|bn| bn as i64),
-            tx_hash,
-            source_token: None,
-            target_token: None,
-            target_amount: None,
-        })
-    }
-
-    pub fn new_swap_deposit(
-        network: &str,
+    },
+    SwapDeposit {
         source_token: Address,
         target_token: Address,
         from: Address,
         to: Address,
         source_amount: U256,
-        nonce: U256,
-        block_number: Option<u64>,
-        tx_hash: Option<String>,
-    ) -> eyre::Result<Self> {
-        Ok(Self {
-            id: None,
-            event_type: "SwapDeposit".to_string(),
-            network: network.to_string(),
-            token_address: format!("{:?}", source_token),
-            from_address: Some(format!("{:?}", from)),
-            to_address: format!("{:?}", to),
-            amount: source_amount.to_string(),
-            nonce: nonce.as_u64() as i64,
-            block_number: block_number.map(|bn| bn as i64),
-            tx_hash,
-            source_token: Some(format!("{:?}", source_token)),
-            target_token: Some(format!("{:?}", target_token)),
-            target_amount: None,
-        })
-    }
-    
-    pub fn new_swap_distribution(
-        network: &str,
+    },
+    SwapDistribution {
         source_token: Address,
         target_token: Address,
         to: Address,
         source_amount: U256,
         target_amount: U256,
+    },
+}
+
+impl BridgeEventKind {
+    pub fn event_type(&self) -> BridgeEventType {
+        match self {
+            BridgeEventKind::Deposit { .. } => BridgeEventType::Deposit,
+            BridgeEventKind::Distribution { .. } => BridgeEventType::Distribution,
+            BridgeEventKind::SwapDeposit { .. } => BridgeEventType::SwapDeposit,
+            BridgeEventKind::SwapDistribution { .. } => BridgeEventType::SwapDistribution,
+        }
+    }
+}
+
+/// Confirmation state of a stored event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BridgeEventStatus {
+    /// Deposit seen on chain but not yet deep enough to be relayed
+    Pending,
+    /// Final, deposits in this state are relayed
+    Confirmed,
+    /// Deposit indexed from history, never relayed
+    Historical,
+}
+
+impl std::fmt::Display for BridgeEventStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BridgeEventStatus::Pending => write!(f, "Pending"),
+            BridgeEventStatus::Confirmed => write!(f, "Confirmed"),
+            BridgeEventStatus::Historical => write!(f, "Historical"),
+        }
+    }
+}
+
+impl FromStr for BridgeEventStatus {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> eyre::Result<Self> {
+        match s {
+            "Pending" => Ok(BridgeEventStatus::Pending),
+            "Confirmed" => Ok(BridgeEventStatus::Confirmed),
+            "Historical" => Ok(BridgeEventStatus::Historical),
+            _ => Err(eyre::eyre!("Unknown bridge event status {}", s)),
+        }
+    }
+}
+
+text_column!(BridgeEventStatus);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BridgeEvent {
+    pub id: Option<i32>,
+    pub event_type: BridgeEventType,
+    pub network: String,
+    pub token_address: String,
+    pub from_address: Option<String>,
+    pub to_address: String,
+    pub amount: TokenAmount,
+    pub nonce: DepositNonce,
+    pub block_number: Option<i64>,
+    pub tx_hash: Option<String>,
+    pub block_hash: Option<String>,
+    pub log_index: Option<i64>,
+    pub source_token: Option<String>,
+    pub target_token: Option<String>,
+    pub target_amount: Option<TokenAmount>,
+    pub status: BridgeEventStatus,
+}
+
+impl BridgeEvent {
+    /// Row of a newly indexed event; deposits wait for confirmations, distributions are final
+    pub fn new(
+        network: &str,
+        kind: BridgeEventKind,
         nonce: U256,
         block_number: Option<u64>,
         tx_hash: Option<String>,
-    ) -> eyre::Result<Self> {
-        Ok(Self {
+    ) -> Self {
+        let event_type = kind.event_type();
+        let (token, from, to, amount, source_token, target_token, target_amount) = match kind {
+            BridgeEventKind::Deposit { token, from, to, amount } =>
+                (token, Some(from), to, amount, None, None, None),
+            BridgeEventKind::Distribution { token, to, amount } =>
+                (token, None, to, amount, None, None, None),
+            BridgeEventKind::SwapDeposit { source_token, target_token, from, to, source_amount } =>
+                (source_token, Some(from), to, source_amount, Some(source_token), Some(target_token), None),
+            BridgeEventKind::SwapDistribution { source_token, target_token, to, source_amount, target_amount } =>
+                (target_token, None, to, source_amount, Some(source_token), Some(target_token), Some(target_amount)),
+        };
+        let status = if event_type.is_deposit() { BridgeEventStatus::Pending } else { BridgeEventStatus::Confirmed };
+
+        Self {
             id: None,
-            event_type: "SwapDistribution".to_string(),
+            event_type,
             network: network.to_string(),
-            token_address: format!("{:?}", target_token),
-            from_address: None,
+            token_address: format!("{:?}", token),
+            from_address: from.map(|from| format!("{:?}", from)),
             to_address: format!("{:?}", to),
-            amount: source_amount.to_string(),
-            nonce: nonce.as_u64() as i64,
+            amount: amount.into(),
+            nonce: nonce.into(),
             block_number: block_number.map(|bn| bn as i64),
             tx_hash,
-            source_token: Some(format!("{:?}", source_token)),
-            target_token: Some(format!("{:?}", target_token)),
-            target_amount: Some(target_amount.to_string()),
-        })
+            block_hash: None,
+            log_index: None,
+            source_token: source_token.map(|token| format!("{:?}", token)),
+            target_token: target_token.map(|token| format!("{:?}", token)),
+            target_amount: target_amount.map(TokenAmount::from),
+            status,
+        }
+    }
+
+    /// Typed view of the row, fails if its columns do not fit its event type
+    pub fn kind(&self) -> eyre::Result<BridgeEventKind> {
+        let describe = || format!("{} {} event with nonce {}", self.network, self.event_type, self.nonce);
+        let address = |value: &str| Address::from_str(value)
+            .map_err(|e| eyre::eyre!("{}: invalid address {}: {:?}", describe(), value, e));
+        let required = |name: &str, value: Option<&String>| value
+            .ok_or_else(|| eyre::eyre!("{}: {} is missing", describe(), name))
+            .and_then(|value| address(value));
+        let absent = |name: &str, present: bool| if present {
+            Err(eyre::eyre!("{}: {} is not set on this event type", describe(), name))
+        } else {
+            Ok(())
+        };
+
+        let token = address(&self.token_address)?;
+        let to = address(&self.to_address)?;
+        let amount = self.amount.0;
+
+        let kind = match self.event_type {
+            BridgeEventType::Deposit | BridgeEventType::Distribution => {
+                absent("source_token", self.source_token.is_some())?;
+                absent("target_token", self.target_token.is_some())?;
+                absent("target_amount", self.target_amount.is_some())?;
+                if self.event_type == BridgeEventType::Deposit {
+                    let from = required("from_address", self.from_address.as_ref())?;
+                    BridgeEventKind::Deposit { token, from, to, amount }
+                } else {
+                    absent("from_address", self.from_address.is_some())?;
+                    BridgeEventKind::Distribution { token, to, amount }
+                }
+            }
+            BridgeEventType::SwapDeposit => {
+                let source_token = required("source_token", self.source_token.as_ref())?;
+                let target_token = required("target_token", self.target_token.as_ref())?;
+                let from = required("from_address", self.from_address.as_ref())?;
+                absent("target_amount", self.target_amount.is_some())?;
+                if token != source_token {
+                    return Err(eyre::eyre!("{}: token_address is not the source token", describe()));
+                }
+                BridgeEventKind::SwapDeposit { source_token, target_token, from, to, source_amount: amount }
+            }
+            BridgeEventType::SwapDistribution => {
+                let source_token = required("source_token", self.source_token.as_ref())?;
+                let target_token = required("target_token", self.target_token.as_ref())?;
+                absent("from_address", self.from_address.is_some())?;
+                let target_amount = self.target_amount
+                    .ok_or_else(|| eyre::eyre!("{}: target_amount is missing", describe()))?;
+                if token != target_token {
+                    return Err(eyre::eyre!("{}: token_address is not the target token", describe()));
+                }
+                BridgeEventKind::SwapDistribution {
+                    source_token, target_token, to, source_amount: amount, target_amount: target_amount.0
+                }
+            }
+        };
+        Ok(kind)
     }
-} 
\ No newline at end of file
+}
@@ -0,0 +1,40 @@
+use serde::{Serialize, Deserialize};
+
+/// Last log fully processed by the indexer for a network and contract
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexerCheckpoint {
+    pub network: String,
+    pub contract_address: String,
+    pub block_number: i64,
+    /// None when every log of `block_number` has been processed
+    pub log_index: Option<i64>,
+    /// Head of the chain when the history was backfilled on the first run, deposits up to this
+    /// block are stored as historical and never relayed
+    pub history_end: Option<i64>,
+}
+
+impl IndexerCheckpoint {
+    pub fn new(network: &str, contract_address: &str, block_number: u64, log_index: Option<u64>) -> Self {
+        Self {
+            network: network.to_string(),
+            contract_address: contract_address.to_string(),
+            block_number: block_number as i64,
+            log_index: log_index.map(|li| li as i64),
+            history_end: None,
+        }
+    }
+
+    /// Whether the log at this position was already processed
+    pub fn covers(&self, block_number: u64, log_index: Option<u64>) -> bool {
+        let block_number = block_number as i64;
+        if block_number != self.block_number {
+            return block_number < self.block_number;
+        }
+
+        match (self.log_index, log_index) {
+            (None, _) => true,
+            (Some(checkpoint_index), Some(index)) => index as i64 <= checkpoint_index,
+            (Some(_), None) => false,
+        }
+    }
+}
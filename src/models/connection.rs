@@ -0,0 +1,51 @@
+use serde::{Serialize, Deserialize};
+use chrono::{DateTime, Utc};
+
+/// Connection state of a network indexer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConnectionState {
+    /// Connecting to the WebSocket provider and catching up on missed blocks
+    Connecting,
+    /// Subscribed and indexing live events
+    Connected,
+    /// The connection was lost, waiting before reconnecting
+    Disconnected,
+}
+
+impl std::fmt::Display for ConnectionState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConnectionState::Connecting => write!(f, "Connecting"),
+            ConnectionState::Connected => write!(f, "Connected"),
+            ConnectionState::Disconnected => write!(f, "Disconnected"),
+        }
+    }
+}
+
+impl std::str::FromStr for ConnectionState {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> eyre::Result<Self> {
+        match s {
+            "Connecting" => Ok(ConnectionState::Connecting),
+            "Connected" => Ok(ConnectionState::Connected),
+            "Disconnected" => Ok(ConnectionState::Disconnected),
+            _ => Err(eyre::eyre!("Unknown connection state {}", s)),
+        }
+    }
+}
+
+text_column!(ConnectionState);
+
+/// Last reported connection state of the indexer of a network
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexerConnection {
+    pub network: String,
+    pub state: ConnectionState,
+    /// Consecutive sessions that ended without staying connected
+    pub failures: i32,
+    pub last_error: Option<String>,
+    /// When the current live session started
+    pub connected_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -1,4 +1,42 @@
+// Store an enum with Display and FromStr implementations in a TEXT column
+macro_rules! text_column {
+    ($name:ty) => {
+        impl sqlx::Type<sqlx::Postgres> for $name {
+            fn type_info() -> sqlx::postgres::PgTypeInfo {
+                <String as sqlx::Type<sqlx::Postgres>>::type_info()
+            }
+
+            fn compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
+                <String as sqlx::Type<sqlx::Postgres>>::compatible(ty)
+            }
+        }
+
+        impl<'r> sqlx::Decode<'r, sqlx::Postgres> for $name {
+            fn decode(value: sqlx::postgres::PgValueRef<'r>) -> Result<Self, sqlx::error::BoxDynError> {
+                let value = <&str as sqlx::Decode<sqlx::Postgres>>::decode(value)?;
+                Ok(<$name as std::str::FromStr>::from_str(value).map_err(|e| e.to_string())?)
+            }
+        }
+
+        impl sqlx::Encode<'_, sqlx::Postgres> for $name {
+            fn encode_by_ref(
+                &self,
+                buf: &mut sqlx::postgres::PgArgumentBuffer,
+            ) -> Result<sqlx::encode::IsNull, sqlx::error::BoxDynError> {
+                <String as sqlx::Encode<sqlx::Postgres>>::encode(self.to_string(), buf)
+            }
+        }
+    };
+}
+
+pub mod numeric;
 pub mod bridge;
+pub mod checkpoint;
+pub mod reorg;
+pub mod relay;
+pub mod connection;
+pub mod transfer;
+pub mod reconciliation;
 
 pub struct AppState {
     pub db: sqlx::PgPool,
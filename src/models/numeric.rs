@@ -0,0 +1,111 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::postgres::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueRef};
+use sqlx::types::BigDecimal;
+use sqlx::{Decode, Encode, Postgres, Type};
+use std::str::FromStr;
+use web3::types::U256;
+
+// A uint256 stored as NUMERIC(78,0) and serialized as a decimal string, so no value loses precision
+macro_rules! numeric_u256 {
+    ($(#[$doc:meta])* $name:ident, $what:literal) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(pub U256);
+
+        impl $name {
+            pub fn to_decimal(&self) -> BigDecimal {
+                BigDecimal::from_str(&self.0.to_string()).expect("a uint256 is a valid decimal")
+            }
+
+            pub fn from_decimal(decimal: &BigDecimal) -> eyre::Result<Self> {
+                if !decimal.is_integer() {
+                    return Err(eyre::eyre!("{} {} is not a whole number", $what, decimal));
+                }
+                let (digits, _) = decimal.with_scale(0).as_bigint_and_exponent();
+                Self::from_str(&digits.to_string())
+            }
+        }
+
+        impl From<U256> for $name {
+            fn from(value: U256) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for U256 {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = eyre::Report;
+
+            fn from_str(s: &str) -> eyre::Result<Self> {
+                U256::from_dec_str(s)
+                    .map(Self)
+                    .map_err(|e| eyre::eyre!("Invalid {} {}: {:?}", $what, s, e))
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(&self.0)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = String::deserialize(deserializer)?;
+                Self::from_str(&value).map_err(serde::de::Error::custom)
+            }
+        }
+
+        impl Type<Postgres> for $name {
+            fn type_info() -> PgTypeInfo {
+                <BigDecimal as Type<Postgres>>::type_info()
+            }
+
+            fn compatible(ty: &PgTypeInfo) -> bool {
+                <BigDecimal as Type<Postgres>>::compatible(ty)
+            }
+        }
+
+        impl PgHasArrayType for $name {
+            fn array_type_info() -> PgTypeInfo {
+                <BigDecimal as PgHasArrayType>::array_type_info()
+            }
+        }
+
+        impl<'r> Decode<'r, Postgres> for $name {
+            fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+                let decimal = <BigDecimal as Decode<Postgres>>::decode(value)?;
+                Ok(Self::from_decimal(&decimal).map_err(|e| e.to_string())?)
+            }
+        }
+
+        impl Encode<'_, Postgres> for $name {
+            fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+                <BigDecimal as Encode<Postgres>>::encode_by_ref(&self.to_decimal(), buf)
+            }
+        }
+    };
+}
+
+numeric_u256!(
+    /// A token amount in the token's smallest unit
+    TokenAmount, "token amount"
+);
+
+numeric_u256!(
+    /// Nonce the bridge contract assigns to a deposit, repeated by its distribution
+    DepositNonce, "deposit nonce"
+);
@@ -0,0 +1,80 @@
+use serde::{Serialize, Deserialize};
+use chrono::{DateTime, Utc};
+
+use crate::models::numeric::DepositNonce;
+
+/// What a reconciliation run found wrong with a transfer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiscrepancyKind {
+    /// Confirmed deposit without a distribution after the grace period
+    UnpaidDeposit,
+    /// Distribution without a deposit on any chain routed to its network
+    OrphanDistribution,
+    /// Distribution paid to another address than the deposit's recipient
+    RecipientMismatch,
+    /// Distribution amount differs from the deposit converted along its route
+    AmountMismatch,
+    /// Distribution paid in another token than the route's target token
+    TokenMismatch,
+}
+
+impl std::fmt::Display for DiscrepancyKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DiscrepancyKind::UnpaidDeposit => write!(f, "UnpaidDeposit"),
+            DiscrepancyKind::OrphanDistribution => write!(f, "OrphanDistribution"),
+            DiscrepancyKind::RecipientMismatch => write!(f, "RecipientMismatch"),
+            DiscrepancyKind::AmountMismatch => write!(f, "AmountMismatch"),
+            DiscrepancyKind::TokenMismatch => write!(f, "TokenMismatch"),
+        }
+    }
+}
+
+impl std::str::FromStr for DiscrepancyKind {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> eyre::Result<Self> {
+        match s {
+            "UnpaidDeposit" => Ok(DiscrepancyKind::UnpaidDeposit),
+            "OrphanDistribution" => Ok(DiscrepancyKind::OrphanDistribution),
+            "RecipientMismatch" => Ok(DiscrepancyKind::RecipientMismatch),
+            "AmountMismatch" => Ok(DiscrepancyKind::AmountMismatch),
+            "TokenMismatch" => Ok(DiscrepancyKind::TokenMismatch),
+            _ => Err(eyre::eyre!("Unknown discrepancy kind {}", s)),
+        }
+    }
+}
+
+text_column!(DiscrepancyKind);
+
+/// A discrepancy between the deposits and the distributions of the bridge.
+/// `network` and `nonce` are the deposit's, or the distribution's for orphan distributions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Discrepancy {
+    pub id: Option<i32>,
+    pub kind: DiscrepancyKind,
+    pub network: String,
+    pub nonce: DepositNonce,
+    pub tx_hash: Option<String>,
+    pub details: String,
+    pub first_seen_at: Option<DateTime<Utc>>,
+    pub last_seen_at: Option<DateTime<Utc>>,
+    /// Set once a run no longer finds the discrepancy
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl Discrepancy {
+    pub fn new(kind: DiscrepancyKind, network: &str, nonce: DepositNonce, tx_hash: Option<String>, details: String) -> Self {
+        Self {
+            id: None,
+            kind,
+            network: network.to_string(),
+            nonce,
+            tx_hash,
+            details,
+            first_seen_at: None,
+            last_seen_at: None,
+            resolved_at: None,
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use serde::{Serialize, Deserialize};
+use chrono::{DateTime, Utc};
+
+use crate::models::numeric::DepositNonce;
+
+/// Lifecycle of a relay job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RelayJobStatus {
+    /// Intent recorded, the transaction is being signed and sent
+    Claimed,
+    /// The distribution transaction was broadcast
+    Sent,
+    /// The matching distribution event was observed on the target chain
+    Completed,
+    /// The relayer gas balance or the bridge token balance cannot cover the relay yet, retried every poll
+    InsufficientLiquidity,
+    /// An RPC call failed while relaying, retried every poll
+    Retrying,
+    /// No route is configured for the deposited token, or its amount cannot be converted.
+    /// Re-evaluated when the relayer restarts with a new config.
+    Unroutable,
+    /// Signing or broadcasting failed or the distribution reverted, the deposit is not retried automatically
+    Failed,
+}
+
+impl std::fmt::Display for RelayJobStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RelayJobStatus::Claimed => write!(f, "Claimed"),
+            RelayJobStatus::Sent => write!(f, "Sent"),
+            RelayJobStatus::Completed => write!(f, "Completed"),
+            RelayJobStatus::InsufficientLiquidity => write!(f, "InsufficientLiquidity"),
+            RelayJobStatus::Retrying => write!(f, "Retrying"),
+            RelayJobStatus::Unroutable => write!(f, "Unroutable"),
+            RelayJobStatus::Failed => write!(f, "Failed"),
+        }
+    }
+}
+
+/// Relay of one deposit to its target chain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelayJob {
+    pub id: i32,
+    pub source_network: String,
+    pub deposit_nonce: DepositNonce,
+    pub deposit_tx_hash: Option<String>,
+    /// None for unroutable deposits
+    pub target_network: Option<String>,
+    pub status: String,
+    pub tx_hash: Option<String>,
+    pub distribution_tx_hash: Option<String>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Outcome of one broadcast relay transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RelayTransactionStatus {
+    /// Broadcast and waiting to be mined
+    Pending,
+    /// Superseded by a fee-bumped transaction with the same nonce
+    Replaced,
+    /// Mined and executed successfully
+    Succeeded,
+    /// Mined but reverted
+    Reverted,
+    /// Its nonce was used by another transaction, it will never be mined
+    Dropped,
+}
+
+impl std::fmt::Display for RelayTransactionStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RelayTransactionStatus::Pending => write!(f, "Pending"),
+            RelayTransactionStatus::Replaced => write!(f, "Replaced"),
+            RelayTransactionStatus::Succeeded => write!(f, "Succeeded"),
+            RelayTransactionStatus::Reverted => write!(f, "Reverted"),
+            RelayTransactionStatus::Dropped => write!(f, "Dropped"),
+        }
+    }
+}
+
+impl std::str::FromStr for RelayTransactionStatus {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> eyre::Result<Self> {
+        match s {
+            "Pending" => Ok(RelayTransactionStatus::Pending),
+            "Replaced" => Ok(RelayTransactionStatus::Replaced),
+            "Succeeded" => Ok(RelayTransactionStatus::Succeeded),
+            "Reverted" => Ok(RelayTransactionStatus::Reverted),
+            "Dropped" => Ok(RelayTransactionStatus::Dropped),
+            _ => Err(eyre::eyre!("Unknown relay transaction status {}", s)),
+        }
+    }
+}
+
+text_column!(RelayTransactionStatus);
+
+/// A distribution transaction broadcast for a relay job.
+/// Everything needed to re-sign it with bumped fees is kept alongside the hash.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelayTransaction {
+    pub id: i32,
+    pub job_id: i32,
+    pub network: String,
+    pub attempt: i32,
+    pub tx_hash: String,
+    pub nonce: i64,
+    pub to_address: String,
+    pub call_data: String,
+    pub gas: i64,
+    pub gas_price: Option<String>,
+    pub max_fee_per_gas: Option<String>,
+    pub max_priority_fee_per_gas: Option<String>,
+    pub status: RelayTransactionStatus,
+    pub block_number: Option<i64>,
+    pub gas_used: Option<i64>,
+    pub sent_at: DateTime<Utc>,
+    pub mined_at: Option<DateTime<Utc>>,
+}
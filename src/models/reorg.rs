@@ -0,0 +1,43 @@
+use serde::{Serialize, Deserialize};
+use chrono::{DateTime, Utc};
+
+/// Header of a recent block as seen by the indexer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexedBlock {
+    pub network: String,
+    pub block_number: i64,
+    pub block_hash: String,
+    pub parent_hash: String,
+}
+
+/// What revealed a chain reorganization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ReorgCause {
+    /// The node re-sent a log with `removed: true`
+    RemovedLog,
+    /// A new head did not build on the block hash we had stored
+    ParentHashMismatch,
+}
+
+impl std::fmt::Display for ReorgCause {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReorgCause::RemovedLog => write!(f, "RemovedLog"),
+            ReorgCause::ParentHashMismatch => write!(f, "ParentHashMismatch"),
+        }
+    }
+}
+
+/// A detected chain reorganization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainReorg {
+    pub id: Option<i32>,
+    pub network: String,
+    pub cause: String,
+    /// First block that is no longer canonical
+    pub fork_block: i64,
+    /// Number of replaced blocks
+    pub depth: i64,
+    pub orphaned_events: i64,
+    pub detected_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,84 @@
+use serde::{Serialize, Deserialize};
+use chrono::{DateTime, Utc};
+
+use crate::models::bridge::BridgeEventType;
+use crate::models::numeric::{DepositNonce, TokenAmount};
+
+/// Where a transfer stands, derived by the `bridge_transfers` view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransferStatus {
+    /// Deposit waiting for confirmations, for the relayer or for liquidity
+    Pending,
+    /// Distribution transaction sent, not yet observed on the target chain
+    Relayed,
+    /// Distribution observed on the target chain
+    Completed,
+    /// Relay failed or the deposit has no route
+    Failed,
+    /// Confirmed more than 30 minutes ago and still not paid out
+    Stuck,
+    /// Deposit indexed from history, never relayed
+    Historical,
+}
+
+impl std::fmt::Display for TransferStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransferStatus::Pending => write!(f, "Pending"),
+            TransferStatus::Relayed => write!(f, "Relayed"),
+            TransferStatus::Completed => write!(f, "Completed"),
+            TransferStatus::Failed => write!(f, "Failed"),
+            TransferStatus::Stuck => write!(f, "Stuck"),
+            TransferStatus::Historical => write!(f, "Historical"),
+        }
+    }
+}
+
+impl std::str::FromStr for TransferStatus {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> eyre::Result<Self> {
+        match s {
+            "Pending" => Ok(TransferStatus::Pending),
+            "Relayed" => Ok(TransferStatus::Relayed),
+            "Completed" => Ok(TransferStatus::Completed),
+            "Failed" => Ok(TransferStatus::Failed),
+            "Stuck" => Ok(TransferStatus::Stuck),
+            "Historical" => Ok(TransferStatus::Historical),
+            _ => Err(eyre::eyre!("Unknown transfer status {}", s)),
+        }
+    }
+}
+
+text_column!(TransferStatus);
+
+/// A deposit paired with its relay and the distribution that paid it out
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BridgeTransfer {
+    pub source_network: String,
+    pub nonce: DepositNonce,
+    pub deposit_type: BridgeEventType,
+    pub source_token: String,
+    pub sender: Option<String>,
+    pub recipient: String,
+    pub amount: TokenAmount,
+    pub deposit_tx_hash: Option<String>,
+    pub deposit_block: Option<i64>,
+    pub deposited_at: Option<DateTime<Utc>>,
+    pub target_network: Option<String>,
+    pub relay_status: Option<String>,
+    pub relay_tx_hash: Option<String>,
+    pub relay_error: Option<String>,
+    pub distribution_tx_hash: Option<String>,
+    pub distribution_block: Option<i64>,
+    pub target_token: Option<String>,
+    pub distributed_amount: Option<TokenAmount>,
+    pub distributed_at: Option<DateTime<Utc>>,
+    pub status: TransferStatus,
+    /// Seconds between indexing the deposit and indexing its distribution
+    pub latency_seconds: Option<i64>,
+    pub distribution_recipient: Option<String>,
+    /// Amount field of the distribution, the source amount of a swap distribution
+    pub distribution_source_amount: Option<TokenAmount>,
+    pub deposit_status: String,
+}
@@ -1,11 +1,13 @@
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgExecutor, PgPool};
 use eyre::Result;
-use crate::models::bridge::BridgeEvent;
+use crate::models::numeric::{DepositNonce, TokenAmount};
+use crate::models::bridge::{BridgeEvent, BridgeEventStatus, BridgeEventType};
+use crate::models::relay::RelayJobStatus;
 
 // Get bridge events from the database
 pub async fn get_bridge_events(
     pool: &PgPool,
-    event_type: Option<String>,
+    event_type: Option<BridgeEventType>,
     network: Option<String>,
     limit: Option<i64>,
     offset: i64,
@@ -13,16 +15,18 @@ pub async fn get_bridge_events(
     let bridge_events = sqlx::query_as!(
         BridgeEvent,
         r#"
-            SELECT id, event_type, network, token_address, from_address, to_address, 
-                amount, nonce, block_number, tx_hash, source_token, target_token, target_amount
+            SELECT id, event_type as "event_type: BridgeEventType", network, token_address, from_address, to_address, 
+                amount as "amount: TokenAmount", nonce as "nonce: DepositNonce", block_number, tx_hash, block_hash, log_index, source_token, target_token,
+                target_amount as "target_amount: TokenAmount", status as "status: BridgeEventStatus"
             FROM bridge_events
-            WHERE ($1::text IS NULL OR event_type = $1)
+            WHERE orphaned = FALSE
+            AND ($1::text IS NULL OR event_type = $1)
             AND ($2::text IS NULL OR network = $2)
             ORDER BY block_number DESC, nonce DESC
             LIMIT $3
             OFFSET $4
         "#,
-        event_type,
+        event_type as _,
         network,
         limit,
         offset
@@ -33,65 +37,157 @@ pub async fn get_bridge_events(
     Ok(bridge_events)
 }
 
-// Save a bridge event to the database
-pub async fn save_bridge_event(pool: &PgPool, event: &BridgeEvent) -> Result<()> {
-    sqlx::query!(
+// Upsert a bridge event keyed by (network, tx_hash, log_index), returns true if the row is new
+pub async fn save_bridge_event<'e, E: PgExecutor<'e>>(executor: E, event: &BridgeEvent) -> Result<bool> {
+    // Rows whose columns do not fit their event type are refused
+    event.kind()?;
+
+    // A redelivered log refreshes its block and is canonical again, its status is kept
+    let inserted = sqlx::query_scalar!(
         r#"
         INSERT INTO bridge_events 
-            (event_type, network, token_address, from_address, to_address, amount, nonce, block_number, tx_hash, 
-             source_token, target_token, target_amount) 
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            (event_type, network, token_address, from_address, to_address, amount, nonce, block_number, tx_hash, block_hash,
+             log_index, source_token, target_token, target_amount, status) 
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+        ON CONFLICT (network, tx_hash, log_index) DO UPDATE
+        SET block_number = EXCLUDED.block_number,
+            block_hash = EXCLUDED.block_hash,
+            orphaned = FALSE
+        RETURNING (xmax = 0) AS "inserted!"
         "#,
-        event.event_type,
+        event.event_type as _,
         event.network,
         event.token_address,
         event.from_address,
         event.to_address,
-        event.amount,
-        event.nonce,
+        event.amount as _,
+        event.nonce as _,
         event.block_number,
         event.tx_hash,
+        event.block_hash,
+        event.log_index,
         event.source_token,
         event.target_token,
-        event.target_amount
+        event.target_amount as _,
+        event.status as _
     )
-    .execute(pool)
+    .fetch_one(executor)
     .await?;
 
-    Ok(())
+    Ok(inserted)
 }
 
-// Save a batch of bridge events to the database
-pub async fn save_batch(pool: &PgPool, events: &[BridgeEvent]) -> Result<()> {
-    // Start a transaction
-    let mut tx = pool.begin().await?;
-
+// Upsert a batch of bridge events within the caller's transaction, returns the number of new rows
+pub async fn save_batch(conn: &mut PgConnection, events: &[BridgeEvent]) -> Result<u64> {
+    let mut inserted = 0;
     for event in events {
-        sqlx::query!(
-            r#"
-            INSERT INTO bridge_events 
-                (event_type, network, token_address, from_address, to_address, amount, nonce, block_number, tx_hash,
-                 source_token, target_token, target_amount) 
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
-            "#,
-            event.event_type,
-            event.network,
-            event.token_address,
-            event.from_address,
-            event.to_address,
-            event.amount,
-            event.nonce,
-            event.block_number,
-            event.tx_hash,
-            event.source_token,
-            event.target_token,
-            event.target_amount
-        )
-        .execute(&mut *tx)
-        .await?;
+        if save_bridge_event(&mut *conn, event).await? {
+            inserted += 1;
+        }
     }
 
-    // Commit the transaction
-    tx.commit().await?;
+    Ok(inserted)
+}
+
+// Mark the events of blocks that left the canonical chain as orphaned
+pub async fn orphan_events_in_blocks(pool: &PgPool, network: &str, block_hashes: &[String]) -> Result<u64> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE bridge_events SET orphaned = TRUE
+        WHERE network = $1 AND block_hash = ANY($2) AND orphaned = FALSE
+        "#,
+        network,
+        block_hashes
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+// Mark the events of a transaction removed from a block as orphaned
+pub async fn orphan_transaction_events(
+    pool: &PgPool,
+    network: &str,
+    tx_hash: &str,
+    block_hash: &str,
+) -> Result<u64> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE bridge_events SET orphaned = TRUE
+        WHERE network = $1 AND tx_hash = $2 AND block_hash = $3 AND orphaned = FALSE
+        "#,
+        network,
+        tx_hash,
+        block_hash
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+// Get the pending deposits of a network mined at or below a block
+pub async fn get_pending_deposits(pool: &PgPool, network: &str, max_block: i64) -> Result<Vec<BridgeEvent>> {
+    let deposits = sqlx::query_as!(
+        BridgeEvent,
+        r#"
+            SELECT id, event_type as "event_type: BridgeEventType", network, token_address, from_address, to_address,
+                amount as "amount: TokenAmount", nonce as "nonce: DepositNonce", block_number, tx_hash, block_hash, log_index, source_token, target_token,
+                target_amount as "target_amount: TokenAmount", status as "status: BridgeEventStatus"
+            FROM bridge_events
+            WHERE network = $1
+            AND status = $2
+            AND orphaned = FALSE
+            AND event_type IN ('Deposit', 'SwapDeposit')
+            AND block_number <= $3
+            ORDER BY block_number ASC, id ASC
+        "#,
+        network,
+        BridgeEventStatus::Pending.to_string(),
+        max_block
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(deposits)
+}
+
+// Update the confirmation status of an event
+pub async fn set_event_status(pool: &PgPool, id: i32, status: BridgeEventStatus) -> Result<()> {
+    sqlx::query!(
+        "UPDATE bridge_events SET status = $1 WHERE id = $2",
+        status as _,
+        id
+    )
+    .execute(pool)
+    .await?;
+
     Ok(())
-} 
\ No newline at end of file
+}
+
+// Get the confirmed deposits of all networks that have no relay job yet or wait for liquidity or a retry
+pub async fn get_unrelayed_deposits(pool: &PgPool) -> Result<Vec<BridgeEvent>> {
+    let deposits = sqlx::query_as!(
+        BridgeEvent,
+        r#"
+            SELECT e.id, e.event_type as "event_type: BridgeEventType", e.network, e.token_address, e.from_address, e.to_address,
+                e.amount as "amount: TokenAmount", e.nonce as "nonce: DepositNonce", e.block_number, e.tx_hash, e.block_hash, e.log_index,
+                e.source_token, e.target_token, e.target_amount as "target_amount: TokenAmount", e.status as "status: BridgeEventStatus"
+            FROM bridge_events e
+            LEFT JOIN relay_jobs j ON j.source_network = e.network AND j.deposit_nonce = e.nonce
+            WHERE e.status = $1
+            AND e.orphaned = FALSE
+            AND e.event_type IN ('Deposit', 'SwapDeposit')
+            AND (j.id IS NULL OR j.status IN ($2, $3))
+            ORDER BY e.block_number ASC, e.id ASC
+        "#,
+        BridgeEventStatus::Confirmed.to_string(),
+        RelayJobStatus::InsufficientLiquidity.to_string(),
+        RelayJobStatus::Retrying.to_string()
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(deposits)
+}
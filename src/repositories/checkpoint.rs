@@ -0,0 +1,52 @@
+use sqlx::{PgExecutor, PgPool};
+use eyre::Result;
+use crate::models::checkpoint::IndexerCheckpoint;
+
+// Get the checkpoint of a network and contract
+pub async fn get_checkpoint(
+    pool: &PgPool,
+    network: &str,
+    contract_address: &str,
+) -> Result<Option<IndexerCheckpoint>> {
+    let checkpoint = sqlx::query_as!(
+        IndexerCheckpoint,
+        r#"
+            SELECT network, contract_address, block_number, log_index, history_end
+            FROM indexer_checkpoints
+            WHERE network = $1 AND contract_address = $2
+        "#,
+        network,
+        contract_address
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(checkpoint)
+}
+
+// Create or move the checkpoint of a network and contract, the history end is only ever set once
+pub async fn save_checkpoint<'e, E: PgExecutor<'e>>(
+    executor: E,
+    checkpoint: &IndexerCheckpoint,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO indexer_checkpoints (network, contract_address, block_number, log_index, history_end)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (network, contract_address) DO UPDATE
+        SET block_number = EXCLUDED.block_number,
+            log_index = EXCLUDED.log_index,
+            history_end = COALESCE(indexer_checkpoints.history_end, EXCLUDED.history_end),
+            updated_at = CURRENT_TIMESTAMP
+        "#,
+        checkpoint.network,
+        checkpoint.contract_address,
+        checkpoint.block_number,
+        checkpoint.log_index,
+        checkpoint.history_end
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
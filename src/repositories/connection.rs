@@ -0,0 +1,75 @@
+use sqlx::PgPool;
+use eyre::Result;
+use crate::models::connection::{ConnectionState, IndexerConnection};
+
+// Record that the indexer of a network is (re)connecting
+pub async fn mark_connecting(pool: &PgPool, network: &str) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO indexer_connections (network, state)
+        VALUES ($1, $2)
+        ON CONFLICT (network) DO UPDATE
+        SET state = EXCLUDED.state, connected_at = NULL, updated_at = CURRENT_TIMESTAMP
+        "#,
+        network,
+        ConnectionState::Connecting.to_string()
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+// Record that the indexer of a network is indexing live events
+pub async fn mark_connected(pool: &PgPool, network: &str) -> Result<()> {
+    sqlx::query!(
+        r#"
+        UPDATE indexer_connections
+        SET state = $1, connected_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP
+        WHERE network = $2
+        "#,
+        ConnectionState::Connected.to_string(),
+        network
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+// Record why the indexer of a network lost its connection
+pub async fn mark_disconnected(pool: &PgPool, network: &str, error: &str, failures: i32) -> Result<()> {
+    sqlx::query!(
+        r#"
+        UPDATE indexer_connections
+        SET state = $1, last_error = $2, failures = $3, connected_at = NULL, updated_at = CURRENT_TIMESTAMP
+        WHERE network = $4
+        "#,
+        ConnectionState::Disconnected.to_string(),
+        error,
+        failures,
+        network
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+// Get the connection state of every indexed network
+pub async fn get_connections(pool: &PgPool) -> Result<Vec<IndexerConnection>> {
+    let connections = sqlx::query_as!(
+        IndexerConnection,
+        r#"
+            SELECT network, state as "state: ConnectionState", failures, last_error,
+                connected_at as "connected_at: chrono::DateTime<chrono::Utc>",
+                updated_at as "updated_at: chrono::DateTime<chrono::Utc>"
+            FROM indexer_connections
+            ORDER BY network
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(connections)
+}
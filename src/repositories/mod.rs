@@ -2,6 +2,13 @@ use sqlx::PgPool;
 use eyre::Result;
 
 pub mod bridge;
+pub mod checkpoint;
+pub mod reorg;
+pub mod relay;
+pub mod nonce;
+pub mod connection;
+pub mod transfer;
+pub mod reconciliation;
 
 pub async fn init_db(database_url: &str) -> Result<PgPool> {
     // Create the connection pool
@@ -0,0 +1,38 @@
+use sqlx::PgPool;
+use eyre::Result;
+
+// Get the persisted next nonce of an account on a network
+pub async fn get_next_nonce(pool: &PgPool, network: &str, address: &str) -> Result<Option<i64>> {
+    let next_nonce = sqlx::query_scalar!(
+        r#"
+        SELECT next_nonce FROM relayer_nonces
+        WHERE network = $1 AND address = $2
+        "#,
+        network,
+        address
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(next_nonce)
+}
+
+// Persist the next nonce of an account on a network
+pub async fn save_next_nonce(pool: &PgPool, network: &str, address: &str, next_nonce: i64) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO relayer_nonces (network, address, next_nonce)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (network, address) DO UPDATE
+        SET next_nonce = EXCLUDED.next_nonce,
+            updated_at = CURRENT_TIMESTAMP
+        "#,
+        network,
+        address,
+        next_nonce
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
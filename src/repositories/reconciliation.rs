@@ -0,0 +1,165 @@
+use sqlx::PgPool;
+use eyre::Result;
+use crate::models::numeric::{DepositNonce, TokenAmount};
+use crate::models::bridge::{BridgeEvent, BridgeEventStatus, BridgeEventType};
+use crate::models::reconciliation::{Discrepancy, DiscrepancyKind};
+use crate::models::transfer::{BridgeTransfer, TransferStatus};
+
+// Transfers whose deposit was relayed or should have been, historical ones are left out
+pub async fn get_transfers_to_reconcile(pool: &PgPool) -> Result<Vec<BridgeTransfer>> {
+    let transfers = sqlx::query_as!(
+        BridgeTransfer,
+        r#"
+            SELECT source_network as "source_network!", nonce as "nonce!: DepositNonce", deposit_type as "deposit_type!: BridgeEventType",
+                source_token as "source_token!", sender, recipient as "recipient!", amount as "amount!: TokenAmount",
+                deposit_tx_hash, deposit_block, deposited_at as "deposited_at: chrono::DateTime<chrono::Utc>",
+                target_network, relay_status, relay_tx_hash, relay_error,
+                distribution_tx_hash, distribution_block, target_token,
+                distributed_amount as "distributed_amount: TokenAmount",
+                distributed_at as "distributed_at: chrono::DateTime<chrono::Utc>",
+                status as "status!: TransferStatus", latency_seconds, distribution_recipient,
+                distribution_source_amount as "distribution_source_amount: TokenAmount",
+                deposit_status as "deposit_status!"
+            FROM bridge_transfers
+            WHERE deposit_status = 'Confirmed'
+            ORDER BY source_network, nonce
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(transfers)
+}
+
+// Distributions indexed longer than the grace period ago that no transfer pairs with a deposit
+pub async fn get_unpaired_distributions(pool: &PgPool, grace_secs: i64) -> Result<Vec<BridgeEvent>> {
+    let distributions = sqlx::query_as!(
+        BridgeEvent,
+        r#"
+            SELECT e.id, e.event_type as "event_type: BridgeEventType", e.network, e.token_address, e.from_address, e.to_address,
+                e.amount as "amount: TokenAmount", e.nonce as "nonce: DepositNonce", e.block_number, e.tx_hash, e.block_hash, e.log_index,
+                e.source_token, e.target_token, e.target_amount as "target_amount: TokenAmount", e.status as "status: BridgeEventStatus"
+            FROM bridge_events e
+            WHERE e.event_type IN ('Distribution', 'SwapDistribution')
+            AND e.orphaned = FALSE
+            AND e.created_at < CURRENT_TIMESTAMP - make_interval(secs => $1)
+            AND NOT EXISTS (
+                SELECT 1 FROM bridge_transfers t
+                WHERE t.target_network = e.network AND t.distribution_tx_hash = e.tx_hash
+            )
+            ORDER BY e.network, e.nonce
+        "#,
+        grace_secs as f64
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(distributions)
+}
+
+// Deposits with the given nonce on any of the networks
+pub async fn get_deposits_by_nonce(pool: &PgPool, networks: &[String], nonce: DepositNonce) -> Result<Vec<BridgeEvent>> {
+    let deposits = sqlx::query_as!(
+        BridgeEvent,
+        r#"
+            SELECT e.id, e.event_type as "event_type: BridgeEventType", e.network, e.token_address, e.from_address, e.to_address,
+                e.amount as "amount: TokenAmount", e.nonce as "nonce: DepositNonce", e.block_number, e.tx_hash, e.block_hash, e.log_index,
+                e.source_token, e.target_token, e.target_amount as "target_amount: TokenAmount", e.status as "status: BridgeEventStatus"
+            FROM bridge_events e
+            WHERE e.network = ANY($1)
+            AND e.nonce = $2
+            AND e.event_type IN ('Deposit', 'SwapDeposit')
+            AND e.orphaned = FALSE
+        "#,
+        networks,
+        nonce as _
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(deposits)
+}
+
+// Record the discrepancies found by a run and resolve the open ones it no longer found.
+// Returns the number of resolved discrepancies.
+pub async fn save_run(pool: &PgPool, found: &[Discrepancy]) -> Result<u64> {
+    let mut tx = pool.begin().await?;
+
+    for discrepancy in found {
+        sqlx::query!(
+            r#"
+            INSERT INTO reconciliation_discrepancies (kind, network, nonce, tx_hash, details)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (kind, network, nonce) DO UPDATE
+            SET tx_hash = EXCLUDED.tx_hash,
+                details = EXCLUDED.details,
+                last_seen_at = CURRENT_TIMESTAMP,
+                first_seen_at = CASE WHEN reconciliation_discrepancies.resolved_at IS NULL
+                    THEN reconciliation_discrepancies.first_seen_at ELSE CURRENT_TIMESTAMP END,
+                resolved_at = NULL
+            "#,
+            discrepancy.kind as _,
+            discrepancy.network,
+            discrepancy.nonce as _,
+            discrepancy.tx_hash,
+            discrepancy.details
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    let kinds: Vec<String> = found.iter().map(|d| d.kind.to_string()).collect();
+    let networks: Vec<String> = found.iter().map(|d| d.network.clone()).collect();
+    let nonces: Vec<DepositNonce> = found.iter().map(|d| d.nonce).collect();
+    let resolved = sqlx::query!(
+        r#"
+        UPDATE reconciliation_discrepancies SET resolved_at = CURRENT_TIMESTAMP
+        WHERE resolved_at IS NULL
+        AND (kind, network, nonce) NOT IN (SELECT * FROM UNNEST($1::text[], $2::text[], $3::numeric[]))
+        "#,
+        &kinds,
+        &networks,
+        &nonces as _
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(resolved.rows_affected())
+}
+
+// Get discrepancies, the open ones unless resolved ones are asked for
+pub async fn get_discrepancies(
+    pool: &PgPool,
+    kind: Option<String>,
+    network: Option<String>,
+    resolved: bool,
+    limit: Option<i64>,
+    offset: i64,
+) -> Result<Vec<Discrepancy>> {
+    let discrepancies = sqlx::query_as!(
+        Discrepancy,
+        r#"
+            SELECT id as "id?", kind as "kind: DiscrepancyKind", network, nonce as "nonce: DepositNonce", tx_hash, details,
+                first_seen_at as "first_seen_at?: chrono::DateTime<chrono::Utc>",
+                last_seen_at as "last_seen_at?: chrono::DateTime<chrono::Utc>",
+                resolved_at as "resolved_at: chrono::DateTime<chrono::Utc>"
+            FROM reconciliation_discrepancies
+            WHERE ($1::text IS NULL OR kind = $1)
+            AND ($2::text IS NULL OR network = $2)
+            AND (resolved_at IS NOT NULL) = $3
+            ORDER BY first_seen_at DESC, id DESC
+            LIMIT $4
+            OFFSET $5
+        "#,
+        kind,
+        network,
+        resolved,
+        limit,
+        offset
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(discrepancies)
+}
@@ -0,0 +1,351 @@
+use sqlx::{PgExecutor, PgPool};
+use eyre::Result;
+use crate::models::numeric::DepositNonce;
+use crate::models::relay::{RelayJobStatus, RelayTransaction, RelayTransactionStatus};
+
+// Record the intent to relay a deposit, or take back a job that was waiting for liquidity or a retry.
+// Returns None if the deposit was already claimed, otherwise the job id and whether it was waiting.
+pub async fn claim_job(
+    pool: &PgPool,
+    source_network: &str,
+    deposit_nonce: DepositNonce,
+    deposit_tx_hash: Option<&str>,
+    target_network: &str,
+) -> Result<Option<(i32, bool)>> {
+    let claim = sqlx::query!(
+        r#"
+        INSERT INTO relay_jobs (source_network, deposit_nonce, deposit_tx_hash, target_network, status)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (source_network, deposit_nonce) DO UPDATE
+        SET status = EXCLUDED.status, updated_at = CURRENT_TIMESTAMP
+        WHERE relay_jobs.status IN ($6, $7)
+        RETURNING id, (xmax <> 0) AS "waiting!"
+        "#,
+        source_network,
+        deposit_nonce as _,
+        deposit_tx_hash,
+        target_network,
+        RelayJobStatus::Claimed.to_string(),
+        RelayJobStatus::InsufficientLiquidity.to_string(),
+        RelayJobStatus::Retrying.to_string()
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(claim.map(|claim| (claim.id, claim.waiting)))
+}
+
+// Record a deposit that cannot be relayed, taking over a job that was waiting for liquidity or a retry.
+// Returns false if the deposit was already claimed.
+pub async fn save_unroutable(
+    pool: &PgPool,
+    source_network: &str,
+    deposit_nonce: DepositNonce,
+    deposit_tx_hash: Option<&str>,
+    reason: &str,
+) -> Result<bool> {
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO relay_jobs (source_network, deposit_nonce, deposit_tx_hash, status, error)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (source_network, deposit_nonce) DO UPDATE
+        SET status = EXCLUDED.status, target_network = NULL, error = EXCLUDED.error, updated_at = CURRENT_TIMESTAMP
+        WHERE relay_jobs.status IN ($6, $7)
+        "#,
+        source_network,
+        deposit_nonce as _,
+        deposit_tx_hash,
+        RelayJobStatus::Unroutable.to_string(),
+        reason,
+        RelayJobStatus::InsufficientLiquidity.to_string(),
+        RelayJobStatus::Retrying.to_string()
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+// Drop the unroutable jobs so their deposits are routed again with the current config
+pub async fn release_unroutable_jobs(pool: &PgPool) -> Result<u64> {
+    let result = sqlx::query!(
+        "DELETE FROM relay_jobs WHERE status = $1",
+        RelayJobStatus::Unroutable.to_string()
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+// Park a job until the relayer or the bridge is funded
+pub async fn mark_insufficient_liquidity(pool: &PgPool, id: i32, reason: &str) -> Result<()> {
+    sqlx::query!(
+        r#"
+        UPDATE relay_jobs SET status = $1, error = $2, updated_at = CURRENT_TIMESTAMP
+        WHERE id = $3
+        "#,
+        RelayJobStatus::InsufficientLiquidity.to_string(),
+        reason,
+        id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+// Park a job whose relay was interrupted by an RPC error, it is picked up again on the next poll
+pub async fn mark_retrying(pool: &PgPool, id: i32, error: &str) -> Result<()> {
+    sqlx::query!(
+        r#"
+        UPDATE relay_jobs SET status = $1, error = $2, updated_at = CURRENT_TIMESTAMP
+        WHERE id = $3
+        "#,
+        RelayJobStatus::Retrying.to_string(),
+        error,
+        id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+// Record the hash of the broadcast distribution transaction
+pub async fn mark_sent(pool: &PgPool, id: i32, tx_hash: &str) -> Result<()> {
+    sqlx::query!(
+        r#"
+        UPDATE relay_jobs SET status = $1, tx_hash = $2, error = NULL, updated_at = CURRENT_TIMESTAMP
+        WHERE id = $3
+        "#,
+        RelayJobStatus::Sent.to_string(),
+        tx_hash,
+        id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+// Record why a relay failed
+pub async fn mark_failed(pool: &PgPool, id: i32, error: &str) -> Result<()> {
+    sqlx::query!(
+        r#"
+        UPDATE relay_jobs SET status = $1, error = $2, updated_at = CURRENT_TIMESTAMP
+        WHERE id = $3
+        "#,
+        RelayJobStatus::Failed.to_string(),
+        error,
+        id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+// Complete the jobs whose distribution has been indexed on the target chain
+pub async fn complete_observed_jobs(pool: &PgPool) -> Result<u64> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE relay_jobs j
+        SET status = $1, distribution_tx_hash = e.tx_hash, updated_at = CURRENT_TIMESTAMP
+        FROM bridge_events e
+        WHERE e.network = j.target_network
+        AND e.nonce = j.deposit_nonce
+        AND e.event_type IN ('Distribution', 'SwapDistribution')
+        AND e.orphaned = FALSE
+        AND j.status <> $1
+        -- Deposits from different chains can share a nonce on the same target chain,
+        -- then only a distribution sent by the job itself completes it
+        AND (
+            NOT EXISTS (
+                SELECT 1 FROM relay_jobs o
+                WHERE o.target_network = j.target_network AND o.deposit_nonce = j.deposit_nonce AND o.id <> j.id
+            )
+            OR e.tx_hash = j.tx_hash
+            OR EXISTS (SELECT 1 FROM relay_transactions t WHERE t.job_id = j.id AND t.tx_hash = e.tx_hash)
+        )
+        "#,
+        RelayJobStatus::Completed.to_string()
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+// Record a broadcast distribution transaction, returns its id
+pub async fn save_transaction<'e, E: PgExecutor<'e>>(executor: E, tx: &RelayTransaction) -> Result<i32> {
+    let id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO relay_transactions (
+            job_id, network, attempt, tx_hash, nonce, to_address, call_data, gas,
+            gas_price, max_fee_per_gas, max_priority_fee_per_gas, status
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+        RETURNING id
+        "#,
+        tx.job_id,
+        tx.network,
+        tx.attempt,
+        tx.tx_hash,
+        tx.nonce,
+        tx.to_address,
+        tx.call_data,
+        tx.gas,
+        tx.gas_price,
+        tx.max_fee_per_gas,
+        tx.max_priority_fee_per_gas,
+        RelayTransactionStatus::Pending.to_string()
+    )
+    .fetch_one(executor)
+    .await?;
+
+    Ok(id)
+}
+
+// Record a distribution transaction before it is broadcast and mark its job sent, returns its id.
+// Whatever happens to the broadcast, the transaction is then tracked until it is mined or dropped.
+pub async fn save_sent_transaction(pool: &PgPool, tx: &RelayTransaction) -> Result<i32> {
+    let mut db_tx = pool.begin().await?;
+
+    let id = save_transaction(&mut *db_tx, tx).await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE relay_jobs SET status = $1, tx_hash = $2, error = NULL, updated_at = CURRENT_TIMESTAMP
+        WHERE id = $3
+        "#,
+        RelayJobStatus::Sent.to_string(),
+        tx.tx_hash,
+        tx.job_id
+    )
+    .execute(&mut *db_tx)
+    .await?;
+
+    db_tx.commit().await?;
+    Ok(id)
+}
+
+// Forget a transaction the node refused, it never left the relayer
+pub async fn delete_transaction(pool: &PgPool, id: i32) -> Result<()> {
+    sqlx::query!("DELETE FROM relay_transactions WHERE id = $1", id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+// Record a fee-bumped replacement and point its job at the new hash
+pub async fn save_replacement(pool: &PgPool, replaced_id: i32, tx: &RelayTransaction) -> Result<i32> {
+    let mut db_tx = pool.begin().await?;
+
+    sqlx::query!(
+        "UPDATE relay_transactions SET status = $1 WHERE id = $2",
+        RelayTransactionStatus::Replaced.to_string(),
+        replaced_id
+    )
+    .execute(&mut *db_tx)
+    .await?;
+
+    let id = save_transaction(&mut *db_tx, tx).await?;
+
+    sqlx::query!(
+        "UPDATE relay_jobs SET tx_hash = $1, updated_at = CURRENT_TIMESTAMP WHERE id = $2",
+        tx.tx_hash,
+        tx.job_id
+    )
+    .execute(&mut *db_tx)
+    .await?;
+
+    db_tx.commit().await?;
+    Ok(id)
+}
+
+// Transactions that may still be mined, grouped by job with the latest attempt last
+pub async fn get_unresolved_transactions(pool: &PgPool, network: &str) -> Result<Vec<RelayTransaction>> {
+    let transactions = sqlx::query_as!(
+        RelayTransaction,
+        r#"
+        SELECT id, job_id, network, attempt, tx_hash, nonce, to_address, call_data, gas,
+               gas_price, max_fee_per_gas, max_priority_fee_per_gas, status as "status: RelayTransactionStatus", block_number, gas_used,
+               sent_at as "sent_at: chrono::DateTime<chrono::Utc>",
+               mined_at as "mined_at: chrono::DateTime<chrono::Utc>"
+        FROM relay_transactions
+        WHERE status IN ($1, $2) AND network = $3
+        ORDER BY job_id, attempt
+        "#,
+        RelayTransactionStatus::Pending.to_string(),
+        RelayTransactionStatus::Replaced.to_string(),
+        network
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(transactions)
+}
+
+// Record the receipt of the mined transaction of a job, the other attempts can no longer be mined
+pub async fn save_receipt(
+    pool: &PgPool,
+    tx: &RelayTransaction,
+    status: RelayTransactionStatus,
+    block_number: i64,
+    gas_used: Option<i64>,
+) -> Result<()> {
+    let mut db_tx = pool.begin().await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE relay_transactions
+        SET status = $1, block_number = $2, gas_used = $3, mined_at = CURRENT_TIMESTAMP
+        WHERE id = $4
+        "#,
+        status.to_string(),
+        block_number,
+        gas_used,
+        tx.id
+    )
+    .execute(&mut *db_tx)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE relay_transactions SET status = $1 WHERE job_id = $2 AND id <> $3 AND status IN ($4, $5)",
+        RelayTransactionStatus::Dropped.to_string(),
+        tx.job_id,
+        tx.id,
+        RelayTransactionStatus::Pending.to_string(),
+        RelayTransactionStatus::Replaced.to_string()
+    )
+    .execute(&mut *db_tx)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE relay_jobs SET tx_hash = $1, updated_at = CURRENT_TIMESTAMP WHERE id = $2",
+        tx.tx_hash,
+        tx.job_id
+    )
+    .execute(&mut *db_tx)
+    .await?;
+
+    db_tx.commit().await?;
+    Ok(())
+}
+
+// Give up on every unmined transaction of a job
+pub async fn drop_transactions(pool: &PgPool, job_id: i32) -> Result<()> {
+    sqlx::query!(
+        "UPDATE relay_transactions SET status = $1 WHERE job_id = $2 AND status IN ($3, $4)",
+        RelayTransactionStatus::Dropped.to_string(),
+        job_id,
+        RelayTransactionStatus::Pending.to_string(),
+        RelayTransactionStatus::Replaced.to_string()
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
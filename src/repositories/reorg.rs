@@ -0,0 +1,115 @@
+use sqlx::PgPool;
+use eyre::Result;
+use crate::models::reorg::{ChainReorg, IndexedBlock};
+
+// Get the stored header of a block
+pub async fn get_block(pool: &PgPool, network: &str, block_number: i64) -> Result<Option<IndexedBlock>> {
+    let block = sqlx::query_as!(
+        IndexedBlock,
+        r#"
+            SELECT network, block_number, block_hash, parent_hash
+            FROM indexed_blocks
+            WHERE network = $1 AND block_number = $2
+        "#,
+        network,
+        block_number
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(block)
+}
+
+// Store a block header, replacing any previous block at the same height
+pub async fn save_block(pool: &PgPool, block: &IndexedBlock) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO indexed_blocks (network, block_number, block_hash, parent_hash)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (network, block_number) DO UPDATE
+        SET block_hash = EXCLUDED.block_hash,
+            parent_hash = EXCLUDED.parent_hash
+        "#,
+        block.network,
+        block.block_number,
+        block.block_hash,
+        block.parent_hash
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+// Forget the headers of blocks older than the reorg window
+pub async fn prune_blocks(pool: &PgPool, network: &str, below_block: i64) -> Result<()> {
+    sqlx::query!(
+        "DELETE FROM indexed_blocks WHERE network = $1 AND block_number < $2",
+        network,
+        below_block
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+// Remove headers from the fork block onwards, returning their hashes
+pub async fn delete_blocks_from(pool: &PgPool, network: &str, from_block: i64) -> Result<Vec<String>> {
+    let hashes = sqlx::query_scalar!(
+        "DELETE FROM indexed_blocks WHERE network = $1 AND block_number >= $2 RETURNING block_hash",
+        network,
+        from_block
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(hashes)
+}
+
+// Record a detected reorg
+pub async fn save_reorg(pool: &PgPool, reorg: &ChainReorg) -> Result<()> {
+    sqlx::query!(
+        r#"
+        INSERT INTO chain_reorgs (network, cause, fork_block, depth, orphaned_events)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+        reorg.network,
+        reorg.cause,
+        reorg.fork_block,
+        reorg.depth,
+        reorg.orphaned_events
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+// Get the reorg history, most recent first
+pub async fn get_reorgs(
+    pool: &PgPool,
+    network: Option<String>,
+    limit: Option<i64>,
+    offset: i64,
+) -> Result<Vec<ChainReorg>> {
+    let reorgs = sqlx::query_as!(
+        ChainReorg,
+        r#"
+            SELECT id as "id?", network, cause, fork_block, depth, orphaned_events,
+                detected_at as "detected_at?: chrono::DateTime<chrono::Utc>"
+            FROM chain_reorgs
+            WHERE ($1::text IS NULL OR network = $1)
+            ORDER BY detected_at DESC, id DESC
+            LIMIT $2
+            OFFSET $3
+        "#,
+        network,
+        limit,
+        offset
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(reorgs)
+}
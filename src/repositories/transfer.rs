@@ -0,0 +1,71 @@
+use sqlx::PgPool;
+use eyre::Result;
+use crate::models::bridge::BridgeEventType;
+use crate::models::numeric::{DepositNonce, TokenAmount};
+use crate::models::transfer::{BridgeTransfer, TransferStatus};
+
+// Get transfers, newest deposits first
+pub async fn get_transfers(
+    pool: &PgPool,
+    network: Option<String>,
+    status: Option<TransferStatus>,
+    limit: Option<i64>,
+    offset: i64,
+) -> Result<Vec<BridgeTransfer>> {
+    let transfers = sqlx::query_as!(
+        BridgeTransfer,
+        r#"
+            SELECT source_network as "source_network!", nonce as "nonce!: DepositNonce", deposit_type as "deposit_type!: BridgeEventType",
+                source_token as "source_token!", sender, recipient as "recipient!", amount as "amount!: TokenAmount",
+                deposit_tx_hash, deposit_block, deposited_at as "deposited_at: chrono::DateTime<chrono::Utc>",
+                target_network, relay_status, relay_tx_hash, relay_error,
+                distribution_tx_hash, distribution_block, target_token,
+                distributed_amount as "distributed_amount: TokenAmount",
+                distributed_at as "distributed_at: chrono::DateTime<chrono::Utc>",
+                status as "status!: TransferStatus", latency_seconds, distribution_recipient,
+                distribution_source_amount as "distribution_source_amount: TokenAmount",
+                deposit_status as "deposit_status!"
+            FROM bridge_transfers
+            WHERE ($1::text IS NULL OR source_network = $1)
+            AND ($2::text IS NULL OR status = $2)
+            ORDER BY deposit_block DESC NULLS LAST, nonce DESC
+            LIMIT $3
+            OFFSET $4
+        "#,
+        network,
+        status as _,
+        limit,
+        offset
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(transfers)
+}
+
+// Get the transfer of the deposit with the given nonce on a source network
+pub async fn get_transfer(pool: &PgPool, source_network: &str, nonce: DepositNonce) -> Result<Option<BridgeTransfer>> {
+    let transfer = sqlx::query_as!(
+        BridgeTransfer,
+        r#"
+            SELECT source_network as "source_network!", nonce as "nonce!: DepositNonce", deposit_type as "deposit_type!: BridgeEventType",
+                source_token as "source_token!", sender, recipient as "recipient!", amount as "amount!: TokenAmount",
+                deposit_tx_hash, deposit_block, deposited_at as "deposited_at: chrono::DateTime<chrono::Utc>",
+                target_network, relay_status, relay_tx_hash, relay_error,
+                distribution_tx_hash, distribution_block, target_token,
+                distributed_amount as "distributed_amount: TokenAmount",
+                distributed_at as "distributed_at: chrono::DateTime<chrono::Utc>",
+                status as "status!: TransferStatus", latency_seconds, distribution_recipient,
+                distribution_source_amount as "distribution_source_amount: TokenAmount",
+                deposit_status as "deposit_status!"
+            FROM bridge_transfers
+            WHERE source_network = $1 AND nonce = $2
+        "#,
+        source_network,
+        nonce as _
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(transfer)
+}
@@ -1,559 +1,800 @@
 use web3::{
-    types::{Address, FilterBuilder, TransactionParameters, U256, Bytes},
+    types::{Address, BlockHeader, BlockId, BlockNumber, FilterBuilder, H256, Log},
     Web3,
 };
+use web3::api::SubscriptionStream;
 use web3::transports::{WebSocket, Http};
 use futures::StreamExt;
 use eyre::Result;
 use std::env;
-use web3::ethabi::{RawLog, Token, Function, Param, ParamType};
+use web3::ethabi::{Event, RawLog};
 use crate::utils::abi::load_abi;
 use std::path::Path;
 use std::time::Duration;
 use sqlx::postgres::PgPool;
 use std::str::FromStr;
-use secp256k1::{PublicKey, SecretKey, Secp256k1, Message};
-use rlp::RlpStream;
 
-use crate::models::bridge::BridgeEvent;
+use crate::models::bridge::{BridgeEvent, BridgeEventKind, BridgeEventStatus};
+use crate::models::checkpoint::IndexerCheckpoint;
+use crate::models::reorg::{ChainReorg, IndexedBlock, ReorgCause};
 use crate::repositories::bridge as bridge_repo;
-use crate::utils::config::{contracts, networks};
+use crate::repositories::checkpoint as checkpoint_repo;
+use crate::repositories::connection as connection_repo;
+use crate::repositories::reorg as reorg_repo;
+use crate::utils::config::{ChainConfig, IndexingMode};
 
-// Token addresses for cross-chain distribution
-const SEPOLIA_TOKEN_ADDRESS: &str = "0x4D77a078a8f698b73b449866ec620DbDc921df39";
-const HOLESKY_TOKEN_ADDRESS: &str = "0xFdA8C8E54219577c73C49441E5d86b512ACEfC28";
+// Maximum number of blocks requested per eth_getLogs call during backfill
+const BACKFILL_BLOCK_RANGE: u64 = 2000;
 
-// At the top, add these constants
-const SEPOLIA_CHAIN_ID: u64 = 11155111;
-const HOLESKY_CHAIN_ID: u64 = 17000;
+// Maximum time a live event waits in the buffer before being flushed
+const LIVE_FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+
+// Number of recent block hashes kept to detect reorgs
+const REORG_WINDOW: u64 = 128;
+
+// Delay before reconnecting a lost network indexer, doubled after every failed session
+const RECONNECT_MIN_BACKOFF: Duration = Duration::from_secs(1);
+const RECONNECT_MAX_BACKOFF: Duration = Duration::from_secs(60);
 
 #[derive(Clone)]
 pub struct BridgeIndexerConfig {
+    /// Maximum number of events written per database transaction
     pub batch_size: u64,
+    /// Days of history to backfill before going live
+    pub days: Option<u64>,
+    /// Block to start the backfill from (overrides days)
+    pub start_block: Option<u64>,
 }
 
 impl Default for BridgeIndexerConfig {
     fn default() -> Self {
         Self {
             batch_size: 100,
+            days: None,
+            start_block: None,
         }
     }
 }
 
-// Convert public key to Ethereum address
-fn public_key_to_address(pubkey: &PublicKey) -> Address {
-    let pubkey_bytes = pubkey.serialize_uncompressed();
-    let hash = web3::signing::keccak256(&pubkey_bytes[1..]); // Skip the 0x04 prefix
-    Address::from_slice(&hash[12..]) // Last 20 bytes
+// The four events emitted by the bridge contract
+struct BridgeEvents {
+    deposit: Event,
+    distribution: Event,
+    swap_deposit: Event,
+    swap_distribution: Event,
 }
 
-// Encode unsigned transaction for signing (EIP-155)
-fn encode_unsigned_transaction(tx: &TransactionParameters, chain_id: u64) -> Vec<u8> {
-    let mut stream = RlpStream::new_list(9);
-    stream.append(&tx.nonce.unwrap_or_default());
-    stream.append(&tx.gas_price.unwrap_or_default());
-    stream.append(&tx.gas);
-    stream.append(&tx.to.unwrap_or_default());
-    stream.append(&tx.value);
-    stream.append(&tx.data.0);
-    stream.append(&chain_id);
-    stream.append(&0u8);
-    stream.append(&0u8);
-    stream.out().to_vec()
-}
+impl BridgeEvents {
+    fn load() -> Result<Self> {
+        let abi_path = Path::new("src/abis/bridge.json");
+        let bridge_abi = load_abi(abi_path)?;
+
+        let event = |name: &str| {
+            bridge_abi.event(name)
+                .cloned()
+                .map_err(|e| eyre::eyre!("Failed to get {} event: {:?}", name, e))
+        };
+
+        Ok(Self {
+            deposit: event("Deposit")?,
+            distribution: event("Distribution")?,
+            swap_deposit: event("SwapDeposit")?,
+            swap_distribution: event("SwapDistribution")?,
+        })
+    }
+
+    // Decode a bridge log into an event record, None if the signature is unknown
+    fn decode(&self, network: &str, log: &Log) -> Result<Option<BridgeEvent>> {
+        let block_hash = log.block_hash.map(|h| format!("{:?}", h));
+        let block_number = log.block_number.map(|bn| bn.as_u64());
+        let tx_hash = log.transaction_hash.map(|h| format!("{:?}", h));
+
+        let first_topic = match log.topics.first() {
+            Some(topic) => *topic,
+            None => return Ok(None),
+        };
+
+        // Convert to raw log for ethabi
+        let raw_log = RawLog {
+            topics: log.topics.clone(),
+            data: log.data.0.clone(),
+        };
+
+        let mut event = if first_topic == self.deposit.signature() {
+            // Decoding the Deposit event
+            let decoded_log = self.deposit.parse_log(raw_log)?;
+            let token = decoded_log.params[0].value.clone().into_address().unwrap();
+            let from = decoded_log.params[1].value.clone().into_address().unwrap();
+            let to = decoded_log.params[2].value.clone().into_address().unwrap();
+            let amount = decoded_log.params[3].value.clone().into_uint().unwrap();
+            let nonce = decoded_log.params[4].value.clone().into_uint().unwrap();
 
-// Encode signed transaction
-fn encode_signed_transaction(tx: &TransactionParameters, v: u64, r: &[u8], s: &[u8]) -> Vec<u8> {
-    let mut stream = RlpStream::new_list(9);
-    stream.append(&tx.nonce.unwrap_or_default());
-    stream.append(&tx.gas_price.unwrap_or_default());
-    stream.append(&tx.gas);
-    stream.append(&tx.to.unwrap_or_default());
-    stream.append(&tx.value);
-    stream.append(&tx.data.0);
-    stream.append(&v);
-    stream.append(&r);
-    stream.append(&s);
-    stream.out().to_vec()
+            println!("  Event:       📥 Deposit");
+            println!("  Token:       {:?}", token);
+            println!("  From:        {:?}", from);
+            println!("  To:          {:?}", to);
+            println!("  Amount:      {}", amount);
+            println!("  Nonce:       {}", nonce);
+
+            let kind = BridgeEventKind::Deposit { token, from, to, amount };
+            BridgeEvent::new(network, kind, nonce, block_number, tx_hash)
+        } else if first_topic == self.distribution.signature() {
+            // Decoding the Distribution event
+            let decoded_log = self.distribution.parse_log(raw_log)?;
+            let token = decoded_log.params[0].value.clone().into_address().unwrap();
+            let to = decoded_log.params[1].value.clone().into_address().unwrap();
+            let amount = decoded_log.params[2].value.clone().into_uint().unwrap();
+            let nonce = decoded_log.params[3].value.clone().into_uint().unwrap();
+
+            println!("  Event:       📤 Distribution");
+            println!("  Token:       {:?}", token);
+            println!("  To:          {:?}", to);
+            println!("  Amount:      {}", amount);
+            println!("  Nonce:       {}", nonce);
+
+            let kind = BridgeEventKind::Distribution { token, to, amount };
+            BridgeEvent::new(network, kind, nonce, block_number, tx_hash)
+        } else if first_topic == self.swap_deposit.signature() {
+            // Decoding the SwapDeposit event
+            let decoded_log = self.swap_deposit.parse_log(raw_log)?;
+            let source_token = decoded_log.params[0].value.clone().into_address().unwrap();
+            let target_token = decoded_log.params[1].value.clone().into_address().unwrap();
+            let from = decoded_log.params[2].value.clone().into_address().unwrap();
+            let to = decoded_log.params[3].value.clone().into_address().unwrap();
+            let source_amount = decoded_log.params[4].value.clone().into_uint().unwrap();
+            let nonce = decoded_log.params[5].value.clone().into_uint().unwrap();
+
+            println!("  Event:         🔄 SwapDeposit");
+            println!("  SourceToken:   {:?}", source_token);
+            println!("  TargetToken:   {:?}", target_token);
+            println!("  From:          {:?}", from);
+            println!("  To:            {:?}", to);
+            println!("  SourceAmount:  {}", source_amount);
+            println!("  Nonce:         {}", nonce);
+
+            let kind = BridgeEventKind::SwapDeposit { source_token, target_token, from, to, source_amount };
+            BridgeEvent::new(network, kind, nonce, block_number, tx_hash)
+        } else if first_topic == self.swap_distribution.signature() {
+            // Decoding the SwapDistribution event
+            let decoded_log = self.swap_distribution.parse_log(raw_log)?;
+            let source_token = decoded_log.params[0].value.clone().into_address().unwrap();
+            let target_token = decoded_log.params[1].value.clone().into_address().unwrap();
+            let to = decoded_log.params[2].value.clone().into_address().unwrap();
+            let source_amount = decoded_log.params[3].value.clone().into_uint().unwrap();
+            let target_amount = decoded_log.params[4].value.clone().into_uint().unwrap();
+            let nonce = decoded_log.params[5].value.clone().into_uint().unwrap();
+
+            println!("  Event:         🔀 SwapDistribution");
+            println!("  SourceToken:   {:?}", source_token);
+            println!("  TargetToken:   {:?}", target_token);
+            println!("  To:            {:?}", to);
+            println!("  SourceAmount:  {}", source_amount);
+            println!("  TargetAmount:  {}", target_amount);
+            println!("  Nonce:         {}", nonce);
+
+            let kind = BridgeEventKind::SwapDistribution { source_token, target_token, to, source_amount, target_amount };
+            BridgeEvent::new(network, kind, nonce, block_number, tx_hash)
+        } else {
+            println!("⚠️ Unknown event signature: {:?}", first_topic);
+            return Ok(None);
+        };
+
+        event.block_hash = block_hash;
+        event.log_index = log.log_index.map(|li| li.as_u64() as i64);
+        Ok(Some(event))
+    }
 }
 
-pub async fn start_bridge_indexer() -> Result<()> {
+pub async fn start_bridge_indexer(chains: Vec<ChainConfig>, config: BridgeIndexerConfig) -> Result<()> {
     // Initialize database connection
     let database_url = env::var("DATABASE_URL")
         .expect("DATABASE_URL must be set in .env file");
     let pool = PgPool::connect(&database_url).await?;
     println!("Connected to PostgreSQL database");
-    
-    // Start indexers for different networks
-    let networks = vec!["sepolia", "holesky"];
-    
-    for network in networks {
-        println!("Starting {} indexer...", network);
-        
-        // Get network-specific address
-        let bridge_address = match network {
-            "sepolia" => contracts::sepolia_bridge_address(),
-            "holesky" => contracts::holesky_bridge_address(),
-            _ => panic!("Unsupported network: {}", network),
-        };
-        
-        // Spawn a task for each network
-        let pool_clone = pool.clone();
-        let network_name = network.to_string();
-        
-        tokio::spawn(async move {
-            // Load ABI inside each task to avoid borrowing issues
-            let abi_path = Path::new("src/abis/bridge.json");
-            match load_abi(abi_path) {
-                Ok(bridge_abi) => {
-                    // Create events inside the task
-                    if let (Ok(deposit_event), Ok(distribution_event)) = (
-                        bridge_abi.event("Deposit"), 
-                        bridge_abi.event("Distribution")
-                    ) {
-                        match monitor_network_events(
-                            &network_name,
-                            bridge_address,
-                            &deposit_event,
-                            &distribution_event,
-                            pool_clone
-                        ).await {
-                            Ok(_) => println!("{} indexer completed successfully", network_name),
-                            Err(e) => eprintln!("{} indexer error: {:?}", network_name, e),
-                        }
-                    } else {
-                        eprintln!("Failed to get events from ABI for {}", network_name);
-                    }
-                },
-                Err(e) => eprintln!("Failed to load ABI for {}: {:?}", network_name, e),
-            }
-        });
+
+    // Start a supervised indexer for every configured chain
+    for chain in chains {
+        println!("Starting {} indexer...", chain.name);
+        tokio::spawn(supervise_network(chain, config.clone(), pool.clone()));
     }
-    
+
     // Keep the main task alive
     loop {
         tokio::time::sleep(Duration::from_secs(60)).await;
     }
 }
 
-async fn monitor_network_events(
+// Keep the indexer of a network running, reconnecting with exponential backoff whenever its
+// session ends. Every new session first backfills the blocks missed while disconnected.
+async fn supervise_network(chain: ChainConfig, config: BridgeIndexerConfig, pool: PgPool) {
+    let network = chain.name.as_str();
+    let mut backoff = RECONNECT_MIN_BACKOFF;
+    let mut failures = 0;
+
+    loop {
+        if let Err(e) = connection_repo::mark_connecting(&pool, network).await {
+            eprintln!("⚠️ Could not record {} connection state: {:?}", network, e);
+        }
+
+        let session_start = tokio::time::Instant::now();
+        let error = match monitor_network_events(&chain, &config, pool.clone()).await {
+            Ok(()) => "event stream ended".to_string(),
+            Err(e) => format!("{:?}", e),
+        };
+
+        // A session that stayed up long enough resets the backoff
+        if session_start.elapsed() >= RECONNECT_MAX_BACKOFF {
+            backoff = RECONNECT_MIN_BACKOFF;
+            failures = 0;
+        }
+        failures += 1;
+
+        eprintln!("🔌 {} indexer disconnected ({}), reconnecting in {:?}", network, error, backoff);
+        if let Err(e) = connection_repo::mark_disconnected(&pool, network, &error, failures).await {
+            eprintln!("⚠️ Could not record {} connection state: {:?}", network, e);
+        }
+
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(RECONNECT_MAX_BACKOFF);
+    }
+}
+
+// Resolve the first block to backfill from, None means live only
+async fn resolve_start_block(
     network: &str,
+    web3: &Web3<Http>,
+    config: &BridgeIndexerConfig
+) -> Result<Option<u64>> {
+    if let Some(start_block) = config.start_block {
+        return Ok(Some(start_block));
+    }
+
+    match config.days {
+        Some(days) if days > 0 => {
+            let now = chrono::Utc::now().timestamp() as u64;
+            let target_timestamp = now.saturating_sub(days * 24 * 60 * 60);
+            let start_block = find_block_by_timestamp(web3, target_timestamp).await?;
+            println!("📅 {} block {} is the first block of the last {} days", network, start_block, days);
+            Ok(Some(start_block))
+        }
+        _ => Ok(None),
+    }
+}
+
+// Binary search for the first block with a timestamp at or after the target
+async fn find_block_by_timestamp(web3: &Web3<Http>, target_timestamp: u64) -> Result<u64> {
+    let mut low = 0u64;
+    let mut high = web3.eth().block_number().await?.as_u64();
+
+    while low < high {
+        let mid = low + (high - low) / 2;
+        let block = web3.eth()
+            .block(BlockId::Number(BlockNumber::Number(mid.into())))
+            .await?
+            .ok_or_else(|| eyre::eyre!("Block {} not found", mid))?;
+
+        if block.timestamp.as_u64() < target_timestamp {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+
+    Ok(low)
+}
+
+// Position of a log within the chain
+fn log_position(log: &Log) -> Option<(u64, Option<u64>)> {
+    let block_number = log.block_number?.as_u64();
+    let log_index = log.log_index.map(|li| li.as_u64());
+    Some((block_number, log_index))
+}
+
+// Indexing state of one network, shared by catch-up and live processing
+pub struct NetworkIndexer {
+    network: String,
     contract_address: Address,
-    deposit_event: &web3::ethabi::Event,
-    distribution_event: &web3::ethabi::Event,
-    pool: PgPool
+    contract: String,
+    events: BridgeEvents,
+    batch_size: usize,
+    confirmations: u64,
+    checkpoint: Option<IndexerCheckpoint>,
+    // Last block of the history backfilled on the first run
+    history_end: Option<u64>,
+    pool: PgPool,
+}
+
+impl NetworkIndexer {
+    // Load the checkpoint of a chain's bridge contract
+    pub async fn new(chain: &ChainConfig, batch_size: u64, pool: PgPool) -> Result<Self> {
+        let contract = format!("{:?}", chain.bridge_address);
+        let checkpoint = checkpoint_repo::get_checkpoint(&pool, &chain.name, &contract).await?;
+
+        Ok(Self {
+            network: chain.name.clone(),
+            contract_address: chain.bridge_address,
+            history_end: checkpoint.as_ref().and_then(|cp| cp.history_end).map(|block| block as u64),
+            checkpoint,
+            contract,
+            events: BridgeEvents::load()?,
+            batch_size: batch_size.max(1) as usize,
+            confirmations: chain.confirmations,
+            pool,
+        })
+    }
+
+    // Index everything from the checkpoint up to the head. Without a checkpoint this is the first
+    // run: the history from start_block is backfilled, or indexing starts at the head.
+    pub async fn catch_up(&mut self, web3: &Web3<Http>, start_block: Option<u64>, head: u64) -> Result<()> {
+        match (self.checkpoint.clone(), start_block) {
+            (Some(cp), _) => {
+                // Also fills the gap left by a lost connection or an interrupted backfill
+                println!("⏩ Resuming {} from checkpoint at block {}", self.network, cp.block_number);
+                self.backfill(web3, cp.block_number as u64, head).await
+            },
+            (None, Some(start_block)) => {
+                // Saved before backfilling, a restart midway still knows which deposits are history
+                let mut start = IndexerCheckpoint::new(&self.network, &self.contract, start_block.saturating_sub(1), None);
+                start.history_end = Some(head as i64);
+                self.flush(&[], Some(start)).await?;
+                self.history_end = Some(head);
+                self.backfill(web3, start_block, head).await
+            },
+            (None, None) => {
+                // First run without history: remember where live indexing began
+                let start = IndexerCheckpoint::new(&self.network, &self.contract, head, None);
+                self.flush(&[], Some(start)).await
+            },
+        }
+    }
+
+    // Whether the log was already persisted according to the checkpoint
+    fn is_processed(&self, position: Option<(u64, Option<u64>)>) -> bool {
+        match (position, self.checkpoint.as_ref()) {
+            (Some((block_number, log_index)), Some(cp)) => cp.covers(block_number, log_index),
+            _ => false,
+        }
+    }
+
+    // Save a batch of events and move the checkpoint past them in a single transaction
+    async fn flush(&mut self, events: &[BridgeEvent], next: Option<IndexerCheckpoint>) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        let inserted = bridge_repo::save_batch(&mut tx, events).await?;
+
+        if let Some(next) = next.as_ref() {
+            checkpoint_repo::save_checkpoint(&mut *tx, next).await?;
+        }
+        tx.commit().await?;
+
+        if next.is_some() {
+            self.checkpoint = next;
+        }
+        if !events.is_empty() {
+            println!("✅ Saved {} {} events to database ({} new)", events.len(), self.network, inserted);
+        }
+        Ok(())
+    }
+
+    // Move the checkpoint back before a block that left the canonical chain
+    async fn rewind(&mut self, fork_block: u64) -> Result<()> {
+        if let Some(cp) = self.checkpoint.as_ref() {
+            if cp.block_number >= fork_block as i64 {
+                let next = IndexerCheckpoint::new(&self.network, &self.contract, fork_block.saturating_sub(1), None);
+                checkpoint_repo::save_checkpoint(&self.pool, &next).await?;
+                self.checkpoint = Some(next);
+            }
+        }
+        Ok(())
+    }
+
+    // Orphan the events of a log the node reports as removed by a reorg
+    async fn handle_removed_log(&mut self, log: &Log) -> Result<()> {
+        let (Some(block_number), Some(tx_hash), Some(block_hash)) =
+            (log.block_number, log.transaction_hash, log.block_hash) else {
+            return Ok(());
+        };
+        let block_number = block_number.as_u64();
+
+        let orphaned = bridge_repo::orphan_transaction_events(
+            &self.pool, &self.network, &format!("{:?}", tx_hash), &format!("{:?}", block_hash)
+        ).await?;
+        println!("♻️ {} log of {:?} removed from block {}, {} events orphaned", self.network, tx_hash, block_number, orphaned);
+
+        if orphaned > 0 {
+            reorg_repo::save_reorg(&self.pool, &ChainReorg {
+                id: None,
+                network: self.network.clone(),
+                cause: ReorgCause::RemovedLog.to_string(),
+                fork_block: block_number as i64,
+                depth: 1,
+                orphaned_events: orphaned as i64,
+                detected_at: None,
+            }).await?;
+        }
+
+        self.rewind(block_number).await
+    }
+
+    // Track a new head and orphan the events of any blocks it replaced
+    pub async fn handle_new_head(&mut self, web3: &Web3<Http>, number: u64, hash: H256, parent_hash: H256) -> Result<()> {
+        let hash = format!("{:?}", hash);
+        let parent_hash = format!("{:?}", parent_hash);
+
+        let parent = reorg_repo::get_block(&self.pool, &self.network, number as i64 - 1).await?;
+        let previous = reorg_repo::get_block(&self.pool, &self.network, number as i64).await?;
+        let reorged = parent.is_some_and(|block| block.block_hash != parent_hash)
+            || previous.is_some_and(|block| block.block_hash != hash);
+
+        if reorged {
+            // Walk back until the stored hash matches the canonical chain again
+            let mut fork_block = number;
+            while fork_block > 0 {
+                let candidate = fork_block - 1;
+                let Some(stored) = reorg_repo::get_block(&self.pool, &self.network, candidate as i64).await? else {
+                    break;
+                };
+                let canonical = web3.eth()
+                    .block(BlockId::Number(BlockNumber::Number(candidate.into())))
+                    .await?
+                    .and_then(|block| block.hash)
+                    .map(|h| format!("{:?}", h));
+                if canonical.as_deref() == Some(stored.block_hash.as_str()) {
+                    break;
+                }
+                fork_block = candidate;
+            }
+
+            let stale_hashes = reorg_repo::delete_blocks_from(&self.pool, &self.network, fork_block as i64).await?;
+            let orphaned = bridge_repo::orphan_events_in_blocks(&self.pool, &self.network, &stale_hashes).await?;
+            println!(
+                "⚠️ {} reorg detected: {} blocks replaced from block {}, {} events orphaned",
+                self.network, stale_hashes.len(), fork_block, orphaned
+            );
+
+            reorg_repo::save_reorg(&self.pool, &ChainReorg {
+                id: None,
+                network: self.network.clone(),
+                cause: ReorgCause::ParentHashMismatch.to_string(),
+                fork_block: fork_block as i64,
+                depth: stale_hashes.len() as i64,
+                orphaned_events: orphaned as i64,
+                detected_at: None,
+            }).await?;
+
+            self.rewind(fork_block).await?;
+        }
+
+        reorg_repo::save_block(&self.pool, &IndexedBlock {
+            network: self.network.clone(),
+            block_number: number as i64,
+            block_hash: hash,
+            parent_hash,
+        }).await?;
+        reorg_repo::prune_blocks(&self.pool, &self.network, number.saturating_sub(REORG_WINDOW) as i64).await?;
+
+        Ok(())
+    }
+
+    // Confirm the pending deposits buried deep enough under the head, the relayer picks them up
+    async fn confirm_deposits(&self, web3: &Web3<Http>, head: u64) -> Result<()> {
+        let Some(max_block) = head.checked_sub(self.confirmations) else {
+            return Ok(());
+        };
+
+        let deposits = bridge_repo::get_pending_deposits(&self.pool, &self.network, max_block as i64).await?;
+        for deposit in deposits {
+            let (Some(id), Some(tx_hash)) = (deposit.id, deposit.tx_hash.as_deref()) else {
+                continue;
+            };
+
+            // The receipt must still point at the block the deposit was indexed in
+            let receipt = web3.eth().transaction_receipt(H256::from_str(tx_hash)?).await?;
+            let canonical_hash = receipt
+                .and_then(|receipt| receipt.block_hash)
+                .map(|h| format!("{:?}", h));
+            if canonical_hash.is_none() || canonical_hash != deposit.block_hash {
+                println!("⏳ {} deposit {} is no longer in block {:?}, waiting for reorg handling", self.network, tx_hash, deposit.block_number);
+                continue;
+            }
+
+            bridge_repo::set_event_status(&self.pool, id, BridgeEventStatus::Confirmed).await?;
+            println!("🔒 {} deposit with nonce {} confirmed after {} blocks", self.network, deposit.nonce, self.confirmations);
+        }
+
+        Ok(())
+    }
+
+    // Fetch, decode and persist all bridge logs in [from_block, to_block]
+    async fn backfill(&mut self, web3: &Web3<Http>, from_block: u64, to_block: u64) -> Result<()> {
+        println!("⏪ Backfilling {} from block {} to {}", self.network, from_block, to_block);
+
+        let mut range_start = from_block;
+        while range_start <= to_block {
+            let range_end = (range_start + BACKFILL_BLOCK_RANGE - 1).min(to_block);
+            let logs = self.index_range(web3, range_start, range_end).await?;
+            println!("📦 {} blocks {}-{}: {} logs", self.network, range_start, range_end, logs);
+            range_start = range_end + 1;
+        }
+
+        println!("✅ {} backfill complete up to block {}", self.network, to_block);
+        Ok(())
+    }
+
+    // Fetch, decode and persist the bridge logs of one eth_getLogs range, returns the number of logs.
+    // Deposits within the history are stored as historical and never relayed.
+    async fn index_range(&mut self, web3: &Web3<Http>, from_block: u64, to_block: u64) -> Result<usize> {
+        let network = self.network.clone();
+        let filter = FilterBuilder::default()
+            .address(vec![self.contract_address])
+            .from_block(BlockNumber::Number(from_block.into()))
+            .to_block(BlockNumber::Number(to_block.into()))
+            .build();
+
+        let logs = web3.eth().logs(filter).await?;
+        let log_count = logs.len();
+
+        let mut batch = Vec::new();
+        for log in logs {
+            let Some((block_number, log_index)) = log_position(&log) else {
+                continue;
+            };
+            if self.is_processed(Some((block_number, log_index))) {
+                continue;
+            }
+
+            match self.events.decode(&network, &log) {
+                Ok(Some(mut event)) => {
+                    let historical = self.history_end.is_some_and(|end| block_number <= end);
+                    if historical && event.status == BridgeEventStatus::Pending {
+                        event.status = BridgeEventStatus::Historical;
+                    }
+                    batch.push(event);
+                },
+                Ok(None) => {},
+                Err(e) => eprintln!("❌ Error decoding {} log: {:?}", network, e),
+            }
+
+            // Large ranges are flushed in batch_size chunks
+            if batch.len() >= self.batch_size {
+                let next = IndexerCheckpoint::new(&network, &self.contract, block_number, log_index);
+                self.flush(&batch, Some(next)).await?;
+                batch.clear();
+            }
+        }
+
+        // The whole range is processed, even the blocks without bridge logs
+        let next = IndexerCheckpoint::new(&network, &self.contract, to_block, None);
+        self.flush(&batch, Some(next)).await?;
+
+        Ok(log_count)
+    }
+}
+
+// Live log and head subscriptions of a network
+struct Subscriptions {
+    logs: SubscriptionStream<WebSocket, Log>,
+    heads: SubscriptionStream<WebSocket, BlockHeader>,
+}
+
+async fn monitor_network_events(
+    chain: &ChainConfig,
+    config: &BridgeIndexerConfig,
+    pool: PgPool,
 ) -> Result<()> {
+    let network = chain.name.as_str();
+    let contract_address = chain.bridge_address;
     println!("🔍 Monitoring {} bridge contract: {:?}", network, contract_address);
-    
-    // Get network-specific WebSocket RPC URL for monitoring
-    let ws_url = networks::get_rpc_url(&format!("{}_WS", network));
-    
-    // Also get HTTP RPC URLs for both networks (for sending transactions)
-    let sepolia_http_url = networks::get_rpc_url("SEPOLIA");
-    let holesky_http_url = networks::get_rpc_url("HOLESKY");
-    
-    // Get private key for transaction signing
-    let private_key = env::var("PRIVATE_KEY")
-        .expect("PRIVATE_KEY must be set in .env file");
-
-    // Remove 0x prefix if present
-    let private_key_hex = if private_key.starts_with("0x") {
-        private_key[2..].to_string()
-    } else {
-        private_key
+
+    let mut indexer = NetworkIndexer::new(chain, config.batch_size, pool).await?;
+
+    // Subscribe to logs before backfilling so no block falls between the two
+    let subscriptions = match (chain.indexing, chain.ws_url.as_deref()) {
+        (IndexingMode::Subscribe, Some(ws_url)) => Some(subscribe(network, ws_url, contract_address).await?),
+        (IndexingMode::Subscribe, None) => return Err(eyre::eyre!("{} has no WebSocket endpoint to subscribe to", network)),
+        (IndexingMode::Poll, _) => None,
+    };
+
+    // Catch up to the current head, live logs covered by the checkpoint are skipped
+    let http_web3 = Web3::new(Http::new(&chain.rpc_url)?);
+    let head = http_web3.eth().block_number().await?.as_u64();
+
+    // The requested history only matters on the first run, later runs resume from the checkpoint
+    let start_block = match indexer.checkpoint {
+        Some(_) => None,
+        None => resolve_start_block(network, &http_web3, config).await?.or(chain.start_block),
     };
+    indexer.catch_up(&http_web3, start_block, head).await?;
+
+    connection_repo::mark_connected(&indexer.pool, network).await?;
+    println!("🟢 {} indexer is live", network);
 
+    match subscriptions {
+        Some(subscriptions) => stream_live_events(&mut indexer, &http_web3, subscriptions).await,
+        None => poll_live_events(&mut indexer, &http_web3, head, chain.poll_interval).await,
+    }
+}
+
+// Open the log and new head subscriptions of a network
+async fn subscribe(network: &str, ws_url: &str, contract_address: Address) -> Result<Subscriptions> {
     // Connect to WebSocket provider for live data
-    let transport = WebSocket::new(&ws_url).await?;
-    let web3 = Web3::new(transport);
+    let web3 = Web3::new(WebSocket::new(ws_url).await?);
     println!("🔌 Connected to WebSocket provider for {} live data", network);
-    
-    // Create filter for both events
+
+    // Create filter for the bridge events
     let filter = FilterBuilder::default()
         .address(vec![contract_address])
         .build();
-    
-    // Subscribe to logs
-    let mut logs_stream = web3.eth_subscribe().subscribe_logs(filter).await?;
+
+    let logs = web3.eth_subscribe().subscribe_logs(filter).await?;
     println!("📡 Subscribed to {} logs", network);
-    
-    // Create HTTP connections for both networks (for sending transactions)
-    let sepolia_http = Http::new(&sepolia_http_url)?;
-    let sepolia_web3 = Web3::new(sepolia_http);
-    
-    let holesky_http = Http::new(&holesky_http_url)?;
-    let holesky_web3 = Web3::new(holesky_http);
-    
-    // Add the new event definitions
-    let abi_path = Path::new("src/abis/bridge.json");
-    let bridge_abi = load_abi(abi_path)?;
-    
-    // Get the swap events from the ABI
-    let swap_deposit_event = bridge_abi.event("SwapDeposit")
-        .map_err(|e| eyre::eyre!("Failed to get SwapDeposit event: {:?}", e))?;
-    let swap_distribution_event = bridge_abi.event("SwapDistribution")
-        .map_err(|e| eyre::eyre!("Failed to get SwapDistribution event: {:?}", e))?;
-    
-    // Get all event signatures
-    let deposit_signature = deposit_event.signature();
-    let distribution_signature = distribution_event.signature();
-    let swap_deposit_signature = swap_deposit_event.signature();
-    let swap_distribution_signature = swap_distribution_event.signature();
-    
-    // Create distribute function signature
-    let distribute_function = Function {
-        name: "distribute".into(),
-        inputs: vec![
-            Param { name: "token".into(), kind: ParamType::Address, internal_type: None },
-            Param { name: "recipient".into(), kind: ParamType::Address, internal_type: None },
-            Param { name: "amount".into(), kind: ParamType::Uint(256), internal_type: None },
-            Param { name: "depositNonce".into(), kind: ParamType::Uint(256), internal_type: None },
-        ],
-        outputs: vec![],
-        constant: false,
-        state_mutability: web3::ethabi::StateMutability::NonPayable,
-    };
-    
-    // Create swap distribute function signature
-    let swap_distribute_function = Function {
-        name: "swapDistribute".into(),
-        inputs: vec![
-            Param { name: "sourceToken".into(), kind: ParamType::Address, internal_type: None },
-            Param { name: "targetToken".into(), kind: ParamType::Address, internal_type: None },
-            Param { name: "recipient".into(), kind: ParamType::Address, internal_type: None },
-            Param { name: "sourceAmount".into(), kind: ParamType::Uint(256), internal_type: None },
-            Param { name: "nonce".into(), kind: ParamType::Uint(256), internal_type: None },
-        ],
-        outputs: vec![],
-        constant: false,
-        state_mutability: web3::ethabi::StateMutability::NonPayable,
-    };
-    
-    // Process logs as they arrive
-    while let Some(log) = logs_stream.next().await {
-        match log {
-            Ok(log) => {
-                println!("\n🔔 {} New event detected!", network);
-                println!("  Block:       {:?}", log.block_number);
-                println!("  Transaction: {:?}", log.transaction_hash);
-                
-                let block_number = log.block_number.map(|bn| bn.as_u64());
-                let tx_hash = log.transaction_hash.map(|h| format!("{:?}", h));
-                
-                // Store the first topic before moving log.topics
-                let first_topic = log.topics[0];
-                
-                // Convert to raw log for ethabi
-                let raw_log = RawLog {
-                    topics: log.topics,
-                    data: log.data.0,
+
+    // New heads are used to track block hashes and detect reorgs
+    let heads = web3.eth_subscribe().subscribe_new_heads().await?;
+
+    Ok(Subscriptions { logs, heads })
+}
+
+// Index the events pushed by the subscriptions until either stream ends
+async fn stream_live_events(
+    indexer: &mut NetworkIndexer,
+    http_web3: &Web3<Http>,
+    subscriptions: Subscriptions,
+) -> Result<()> {
+    let network = indexer.network.clone();
+    let network = network.as_str();
+    let Subscriptions { logs: mut logs_stream, heads: mut heads_stream } = subscriptions;
+
+    // Live events are buffered and flushed by count or after LIVE_FLUSH_INTERVAL
+    let mut pending: Vec<BridgeEvent> = Vec::new();
+    let mut pending_checkpoint: Option<IndexerCheckpoint> = None;
+    let mut flush_timer = tokio::time::interval(LIVE_FLUSH_INTERVAL);
+
+    loop {
+        let flush_now = tokio::select! {
+            log = logs_stream.next() => {
+                let Some(log) = log else {
+                    break;
                 };
-                
-                if first_topic == deposit_signature {
-                    // Decoding the Deposit event
-                    match deposit_event.parse_log(raw_log) {
-                        Ok(decoded_log) => {
-                            // Extract params
-                            let token = decoded_log.params[0].value.clone().into_address().unwrap();
-                            let from = decoded_log.params[1].value.clone().into_address().unwrap();
-                            let to = decoded_log.params[2].value.clone().into_address().unwrap();
-                            let amount = decoded_log.params[3].value.clone().into_uint().unwrap();
-                            let nonce = decoded_log.params[4].value.clone().into_uint().unwrap();
-                            
-                            println!("  Event:       📥 Deposit");
-                            println!("  Token:       {:?}", token);
-                            println!("  From:        {:?}", from);
-                            println!("  To:          {:?}", to);
-                            println!("  Amount:      {}", amount);
-                            println!("  Nonce:       {}", nonce);
-                            
-                            // Create bridge event record and save directly
-                            match BridgeEvent::new_deposit(
-                                network, token, from, to, amount, nonce, block_number, tx_hash.clone()
-                            ) {
-                                Ok(event) => {
-                                    if let Err(e) = bridge_repo::save_bridge_event(&pool, &event).await {
-                                        eprintln!("❌ Error saving deposit event: {:?}", e);
-                                    } else {
-                                        println!("✅ Saved deposit event to database");
-                                    }
-                                },
-                                Err(e) => eprintln!("❌ Error creating deposit event record: {:?}", e),
-                            }
-                            
-                            // Now create a distribution transaction on the other chain
-                            let (target_network, target_web3, target_address, token_address, target_chain_id) = if network == "sepolia" {
-                                ("holesky", &holesky_web3, contracts::holesky_bridge_address(), HOLESKY_TOKEN_ADDRESS, HOLESKY_CHAIN_ID)
-                            } else {
-                                ("sepolia", &sepolia_web3, contracts::sepolia_bridge_address(), SEPOLIA_TOKEN_ADDRESS, SEPOLIA_CHAIN_ID)
-                            };
-                            
-                            println!("\n🔄 Creating distribution transaction on {} network", target_network);
-                            println!("  Target bridge: {:?}", target_address);
-                            println!("  Token:         {}", token_address);
-                            println!("  Recipient:     {:?}", to);
-                            println!("  Amount:        {}", amount);
-                            println!("  Nonce:         {}", nonce);
-
-                            // Create the function call data
-                            let token_address = Address::from_str(token_address).expect("Invalid token address");
-                            let call_data = distribute_function.encode_input(&[
-                                Token::Address(token_address),
-                                Token::Address(to),
-                                Token::Uint(amount),
-                                Token::Uint(nonce),
-                            ])?;
-
-                            println!("📝 Transaction created and ready to send");
-
-                            // Parse the private key and derive the from address
-                            let secp = Secp256k1::new();
-                            let secret_key = match SecretKey::from_str(&private_key_hex) {
-                                Ok(key) => key,
-                                Err(e) => {
-                                    eprintln!("⚠️ Error parsing private key: {:?}", e);
-                                    return Err(e.into());
-                                }
-                            };
-                            let public_key = PublicKey::from_secret_key(&secp, &secret_key);
-                            let from_address = public_key_to_address(&public_key);
-
-                            // Create transaction parameters
-                            let mut tx_request = TransactionParameters {
-                                to: Some(target_address),
-                                data: call_data.clone().into(),
-                                gas: U256::from(300000),
-                                chain_id: Some(target_chain_id),
-                                ..Default::default()
-                            };
-
-                            // Get gas price and nonce from the network
-                            if let Ok(gas_price) = target_web3.eth().gas_price().await {
-                                tx_request.gas_price = Some(gas_price);
-                            }
-                            if let Ok(nonce) = target_web3.eth().transaction_count(from_address, None).await {
-                                tx_request.nonce = Some(nonce);
-                            }
 
-                            // Sign the transaction manually
-                            let unsigned_rlp = encode_unsigned_transaction(&tx_request, target_chain_id);
-                            let hash = web3::signing::keccak256(&unsigned_rlp);
-                            let message = Message::from_slice(&hash)?;
-                            let signature = secp.sign(&message, &secret_key);
-                            let sig_bytes = signature.serialize_compact();
-                            let rec_id = 0; 
-                            let r = &sig_bytes[0..32];
-                            let s = &sig_bytes[32..64];
-                            let v = target_chain_id * 2 + 35 + rec_id as u64; 
-                            let raw_tx = encode_signed_transaction(&tx_request, v, r, s);
-
-                            // Send the raw transaction
-                            match target_web3.eth().send_raw_transaction(raw_tx.into()).await {
-                                Ok(tx_hash) => {
-                                    println!("🚀 Distribution transaction sent: {:?}", tx_hash);
-                                }
-                                Err(e) => {
-                                    eprintln!("⚠️ Error sending transaction: {:?}", e);
-                                    println!("⚠️ Falling back to simulation mode");
-                                    println!("🔄 Would distribute: {} tokens to {:?} with nonce {}", amount, to, nonce);
-                                }
-                            }
-                        },
-                        Err(e) => eprintln!("❌ Error decoding deposit event: {:?}", e),
-                    }
-                } else if first_topic == distribution_signature {
-                    // Decoding the Distribution event
-                    match distribution_event.parse_log(raw_log) {
-                        Ok(decoded_log) => {
-                            // Extract params
-                            let token = decoded_log.params[0].value.clone().into_address().unwrap();
-                            let to = decoded_log.params[1].value.clone().into_address().unwrap();
-                            let amount = decoded_log.params[2].value.clone().into_uint().unwrap();
-                            let nonce = decoded_log.params[3].value.clone().into_uint().unwrap();
-                            
-                            println!("  Event:       📤 Distribution");
-                            println!("  Token:       {:?}", token);
-                            println!("  To:          {:?}", to);
-                            println!("  Amount:      {}", amount);
-                            println!("  Nonce:       {}", nonce);
-                            
-                            // Create bridge event record and save directly
-                            match BridgeEvent::new_distribution(
-                                network, token, to, amount, nonce, block_number, tx_hash
-                            ) {
-                                Ok(event) => {
-                                    if let Err(e) = bridge_repo::save_bridge_event(&pool, &event).await {
-                                        eprintln!("❌ Error saving distribution event: {:?}", e);
-                                    } else {
-                                        println!("✅ Saved distribution event to database");
-                                    }
-                                },
-                                Err(e) => eprintln!("❌ Error creating distribution event record: {:?}", e),
-                            }
-                        },
-                        Err(e) => eprintln!("❌ Error decoding distribution event: {:?}", e),
+                match log {
+                    Ok(log) if log.is_removed() => {
+                        // Buffered copies must reach the database before being orphaned
+                        flush_live_batch(indexer, &mut pending, &mut pending_checkpoint).await?;
+                        if let Err(e) = indexer.handle_removed_log(&log).await {
+                            eprintln!("❌ Error handling removed {} log: {:?}", network, e);
+                        }
+                        false
+                    },
+                    Ok(log) => {
+                        let position = log_position(&log);
+                        if indexer.is_processed(position) {
+                            continue;
+                        }
+
+                        println!("\n🔔 {} New event detected!", network);
+                        println!("  Block:       {:?}", log.block_number);
+                        println!("  Transaction: {:?}", log.transaction_hash);
+
+                        match indexer.events.decode(network, &log) {
+                            Ok(Some(event)) => pending.push(event),
+                            Ok(None) => {},
+                            Err(e) => eprintln!("❌ Error decoding {} log: {:?}", network, e),
+                        }
+
+                        if let Some((block_number, log_index)) = position {
+                            pending_checkpoint = Some(IndexerCheckpoint::new(network, &indexer.contract, block_number, log_index));
+                        }
+
+                        pending.len() >= indexer.batch_size
+                    },
+                    Err(e) => {
+                        eprintln!("❌ Error in {} log stream: {:?}", network, e);
+                        false
                     }
-                } else if first_topic == swap_deposit_signature {
-                    // New SwapDeposit event handling
-                    match swap_deposit_event.parse_log(raw_log) {
-                        Ok(decoded_log) => {
-                            // Extract SwapDeposit params
-                            let source_token = decoded_log.params[0].value.clone().into_address().unwrap();
-                            let target_token = decoded_log.params[1].value.clone().into_address().unwrap();
-                            let from = decoded_log.params[2].value.clone().into_address().unwrap();
-                            let to = decoded_log.params[3].value.clone().into_address().unwrap();
-                            let source_amount = decoded_log.params[4].value.clone().into_uint().unwrap();
-                            let nonce = decoded_log.params[5].value.clone().into_uint().unwrap();
-                            
-                            println!("  Event:         🔄 SwapDeposit");
-                            println!("  SourceToken:   {:?}", source_token);
-                            println!("  TargetToken:   {:?}", target_token);
-                            println!("  From:          {:?}", from);
-                            println!("  To:            {:?}", to);
-                            println!("  SourceAmount:  {}", source_amount);
-                            println!("  Nonce:         {}", nonce);
-                            
-                            // Create bridge event record and save directly
-                            // Note: You'll need to update your BridgeEvent model to support swap events
-                            match BridgeEvent::new_swap_deposit(
-                                network, source_token, target_token, from, to, source_amount, nonce, block_number, tx_hash.clone()
-                            ) {
-                                Ok(event) => {
-                                    if let Err(e) = bridge_repo::save_bridge_event(&pool, &event).await {
-                                        eprintln!("❌ Error saving swap deposit event: {:?}", e);
-                                    } else {
-                                        println!("✅ Saved swap deposit event to database");
-                                    }
-                                },
-                                Err(e) => eprintln!("❌ Error creating swap deposit event record: {:?}", e),
-                            }
-                            
-                            // Create swap distribution transaction on the other chain
-                            let (target_network, target_web3, target_address, source_token_address, target_token_address, target_chain_id) = if network == "sepolia" {
-                                ("holesky", &holesky_web3, contracts::holesky_bridge_address(), SEPOLIA_TOKEN_ADDRESS, HOLESKY_TOKEN_ADDRESS, HOLESKY_CHAIN_ID)
-                            } else {
-                                ("sepolia", &sepolia_web3, contracts::sepolia_bridge_address(), HOLESKY_TOKEN_ADDRESS, SEPOLIA_TOKEN_ADDRESS, SEPOLIA_CHAIN_ID)
-                            };
-                            
-                            println!("\n🔄 Creating swap distribution transaction on {} network", target_network);
-                            println!("  Target bridge:   {:?}", target_address);
-                            println!("  Source Token:    {}", source_token_address);
-                            println!("  Target Token:    {}", target_token_address);
-                            println!("  Recipient:       {:?}", to);
-                            println!("  Source Amount:   {}", source_amount);
-                            println!("  Nonce:           {}", nonce);
-                            
-                            // Create function call data for swapDistribute
-                            let source_token_addr = Address::from_str(source_token_address).expect("Invalid source token address");
-                            let target_token_addr = Address::from_str(target_token_address).expect("Invalid target token address");
-                            
-                            let call_data = swap_distribute_function.encode_input(&[
-                                Token::Address(source_token_addr),
-                                Token::Address(target_token_addr),
-                                Token::Address(to),
-                                Token::Uint(source_amount),
-                                Token::Uint(nonce),
-                            ])?;
-                            
-                            // Parse the private key and derive the from address
-                            let secp = Secp256k1::new();
-                            let secret_key = match SecretKey::from_str(&private_key_hex) {
-                                Ok(key) => key,
-                                Err(e) => {
-                                    eprintln!("⚠️ Error parsing private key: {:?}", e);
-                                    return Err(e.into());
-                                }
-                            };
-                            let public_key = PublicKey::from_secret_key(&secp, &secret_key);
-                            let from_address = public_key_to_address(&public_key);
-
-                            // Create transaction parameters
-                            let mut tx_request = TransactionParameters {
-                                to: Some(target_address),
-                                data: call_data.clone().into(),
-                                gas: U256::from(400000), // May need more gas for swaps
-                                chain_id: Some(target_chain_id),
-                                ..Default::default()
-                            };
-
-                            // Get gas price and nonce
-                            if let Ok(gas_price) = target_web3.eth().gas_price().await {
-                                tx_request.gas_price = Some(gas_price);
-                            }
-                            if let Ok(nonce) = target_web3.eth().transaction_count(from_address, None).await {
-                                tx_request.nonce = Some(nonce);
-                            }
+                }
+            },
+            header = heads_stream.next() => {
+                let Some(header) = header else {
+                    break;
+                };
 
-                            // Sign and send the transaction
-                            let unsigned_rlp = encode_unsigned_transaction(&tx_request, target_chain_id);
-                            let hash = web3::signing::keccak256(&unsigned_rlp);
-                            let message = Message::from_slice(&hash)?;
-                            let signature = secp.sign(&message, &secret_key);
-                            let sig_bytes = signature.serialize_compact();
-                            let rec_id = 0;
-                            let r = &sig_bytes[0..32];
-                            let s = &sig_bytes[32..64];
-                            let v = target_chain_id * 2 + 35 + rec_id as u64;
-                            let raw_tx = encode_signed_transaction(&tx_request, v, r, s);
-
-                            // Send the raw transaction
-                            match target_web3.eth().send_raw_transaction(raw_tx.into()).await {
-                                Ok(tx_hash) => {
-                                    println!("🚀 Swap distribution transaction sent: {:?}", tx_hash);
-                                }
-                                Err(e) => {
-                                    eprintln!("⚠️ Error sending swap transaction: {:?}", e);
-                                }
+                match header {
+                    Ok(header) => {
+                        flush_live_batch(indexer, &mut pending, &mut pending_checkpoint).await?;
+                        if let (Some(number), Some(hash)) = (header.number, header.hash) {
+                            let head = number.as_u64();
+                            if let Err(e) = indexer.handle_new_head(http_web3, head, hash, header.parent_hash).await {
+                                eprintln!("❌ Error checking {} head for reorgs: {:?}", network, e);
                             }
-                        },
-                        Err(e) => eprintln!("❌ Error decoding swap deposit event: {:?}", e),
-                    }
-                } else if first_topic == swap_distribution_signature {
-                    // Handle SwapDistribution event
-                    match swap_distribution_event.parse_log(raw_log) {
-                        Ok(decoded_log) => {
-                            // Extract SwapDistribution params
-                            let source_token = decoded_log.params[0].value.clone().into_address().unwrap();
-                            let target_token = decoded_log.params[1].value.clone().into_address().unwrap();
-                            let to = decoded_log.params[2].value.clone().into_address().unwrap();
-                            let source_amount = decoded_log.params[3].value.clone().into_uint().unwrap();
-                            let target_amount = decoded_log.params[4].value.clone().into_uint().unwrap();
-                            let nonce = decoded_log.params[5].value.clone().into_uint().unwrap();
-                            
-                            println!("  Event:         🔀 SwapDistribution");
-                            println!("  SourceToken:   {:?}", source_token);
-                            println!("  TargetToken:   {:?}", target_token);
-                            println!("  To:            {:?}", to);
-                            println!("  SourceAmount:  {}", source_amount);
-                            println!("  TargetAmount:  {}", target_amount);
-                            println!("  Nonce:         {}", nonce);
-                            
-                            // Create and save event record
-                            match BridgeEvent::new_swap_distribution(
-                                network, source_token, target_token, to, source_amount, target_amount, nonce, block_number, tx_hash.clone()
-                            ) {
-                                Ok(event) => {
-                                    if let Err(e) = bridge_repo::save_bridge_event(&pool, &event).await {
-                                        eprintln!("❌ Error saving swap distribution event: {:?}", e);
-                                    } else {
-                                        println!("✅ Saved swap distribution event to database");
-                                    }
-                                },
-                                Err(e) => eprintln!("❌ Error creating swap distribution event record: {:?}", e),
+                            if let Err(e) = indexer.confirm_deposits(http_web3, head).await {
+                                eprintln!("❌ Error confirming {} deposits: {:?}", network, e);
                             }
-                        },
-                        Err(e) => eprintln!("❌ Error decoding swap distribution event: {:?}", e),
-                    }
-                } else {
-                    println!("⚠️ Unknown event signature: {:?}", first_topic);
+                        }
+                    },
+                    Err(e) => eprintln!("❌ Error in {} head stream: {:?}", network, e),
                 }
-                
-                println!();
+                false
             },
-            Err(e) => eprintln!("❌ Error in {} log stream: {:?}", network, e),
+            _ = flush_timer.tick() => !pending.is_empty(),
+        };
+
+        if flush_now {
+            flush_live_batch(indexer, &mut pending, &mut pending_checkpoint).await?;
         }
     }
-    
-    println!("📢 {} event stream ended", network);
+
+    // Persist whatever was buffered when the stream ended
+    flush_live_batch(indexer, &mut pending, &mut pending_checkpoint).await
+}
+
+// Follow a network over HTTP: on every interval, check the new blocks for reorgs, then index
+// the logs from the checkpoint to the head with the same pipeline as the backfill
+async fn poll_live_events(
+    indexer: &mut NetworkIndexer,
+    http_web3: &Web3<Http>,
+    mut last_head: u64,
+    interval: Duration,
+) -> Result<()> {
+    let network = indexer.network.clone();
+    println!("⏱️ Polling {} every {:?}", network, interval);
+
+    loop {
+        tokio::time::sleep(interval).await;
+
+        let head = http_web3.eth().block_number().await?.as_u64();
+        if head <= last_head {
+            continue;
+        }
+
+        // Only the blocks within the reorg window matter for reorg detection
+        for number in (last_head + 1).max(head.saturating_sub(REORG_WINDOW))..=head {
+            let block = http_web3.eth()
+                .block(BlockId::Number(BlockNumber::Number(number.into())))
+                .await?
+                .ok_or_else(|| eyre::eyre!("{} block {} not found", network, number))?;
+            let Some(hash) = block.hash else {
+                continue;
+            };
+            indexer.handle_new_head(http_web3, number, hash, block.parent_hash).await?;
+        }
+
+        // A reorg may have moved the checkpoint back, the range starts from wherever it is now
+        let from_block = indexer.checkpoint.as_ref().map_or(last_head + 1, |cp| cp.block_number as u64);
+        let mut range_start = from_block;
+        while range_start <= head {
+            let range_end = (range_start + BACKFILL_BLOCK_RANGE - 1).min(head);
+            let logs = indexer.index_range(http_web3, range_start, range_end).await?;
+            if logs > 0 {
+                println!("🔔 {} blocks {}-{}: {} logs", network, range_start, range_end, logs);
+            }
+            range_start = range_end + 1;
+        }
+
+        if let Err(e) = indexer.confirm_deposits(http_web3, head).await {
+            eprintln!("❌ Error confirming {} deposits: {:?}", network, e);
+        }
+        last_head = head;
+    }
+}
+
+// Flush buffered live events, deposits are relayed once confirmed
+async fn flush_live_batch(
+    indexer: &mut NetworkIndexer,
+    pending: &mut Vec<BridgeEvent>,
+    pending_checkpoint: &mut Option<IndexerCheckpoint>
+) -> Result<()> {
+    if pending.is_empty() {
+        return Ok(());
+    }
+
+    // Events stay buffered on failure and are retried on the next flush
+    if let Err(e) = indexer.flush(pending, pending_checkpoint.clone()).await {
+        eprintln!("❌ Error saving {} events: {:?}", indexer.network, e);
+        return Ok(());
+    }
+    *pending_checkpoint = None;
+    pending.clear();
+
+    println!();
     Ok(())
-} 
\ No newline at end of file
+}
@@ -1 +1,4 @@
-pub mod bridge_indexer; 
\ No newline at end of file
+pub mod bridge_indexer;
+pub mod relayer;
+pub mod nonce_manager;
+pub mod reconciler;
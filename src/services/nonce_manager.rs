@@ -0,0 +1,108 @@
+use eyre::Result;
+use sqlx::postgres::PgPool;
+use tokio::sync::Mutex;
+use web3::transports::Http;
+use web3::types::{Address, BlockNumber};
+use web3::Web3;
+
+use crate::repositories::nonce as nonce_repo;
+
+// Hands out the nonces of the relayer account on one network.
+// Nonces are reserved locally so back-to-back relays never share one, and the next nonce is
+// persisted so it survives restarts when the node cannot be reached.
+pub struct NonceManager {
+    network: String,
+    address: Address,
+    web3: Web3<Http>,
+    pool: PgPool,
+    // Next nonce to hand out, None until it has been synced with the node
+    next: Mutex<Option<u64>>,
+}
+
+impl NonceManager {
+    pub fn new(network: &str, address: Address, web3: Web3<Http>, pool: PgPool) -> Self {
+        Self {
+            network: network.to_string(),
+            address,
+            web3,
+            pool,
+            next: Mutex::new(None),
+        }
+    }
+
+    // Resync the next nonce from the node's pending transaction count
+    pub async fn sync(&self) -> Result<u64> {
+        let mut next = self.next.lock().await;
+        let nonce = self.load().await?;
+        self.persist(nonce).await?;
+        *next = Some(nonce);
+        Ok(nonce)
+    }
+
+    // Reserve the next nonce, syncing first if the local nonce was invalidated
+    pub async fn reserve(&self) -> Result<u64> {
+        let mut next = self.next.lock().await;
+        let nonce = match *next {
+            Some(nonce) => nonce,
+            None => self.load().await?,
+        };
+        self.persist(nonce + 1).await?;
+        *next = Some(nonce + 1);
+        Ok(nonce)
+    }
+
+    // Give back a nonce whose transaction never left the relayer
+    pub async fn release(&self, nonce: u64) -> Result<()> {
+        let mut next = self.next.lock().await;
+        if *next == Some(nonce + 1) {
+            self.persist(nonce).await?;
+            *next = Some(nonce);
+        } else {
+            // A later nonce is already out, so this one is a gap the node has to tell us about
+            *next = None;
+        }
+        Ok(())
+    }
+
+    // Forget the local nonce so the next reservation resyncs from the node
+    pub async fn invalidate(&self) {
+        *self.next.lock().await = None;
+    }
+
+    // Pending transaction count of the account, or the persisted nonce if the node is unreachable
+    async fn load(&self) -> Result<u64> {
+        let address = format!("{:?}", self.address);
+        let persisted = nonce_repo::get_next_nonce(&self.pool, &self.network, &address).await?;
+
+        match self.web3.eth().transaction_count(self.address, Some(BlockNumber::Pending)).await {
+            Ok(count) => {
+                let pending = count.as_u64();
+                if let Some(persisted) = persisted.filter(|persisted| *persisted as u64 != pending) {
+                    println!(
+                        "🔢 {} nonce resynced from {} to {} (pending on node)",
+                        self.network, persisted, pending
+                    );
+                }
+                Ok(pending)
+            }
+            Err(e) => match persisted {
+                Some(persisted) => {
+                    eprintln!("⚠️ Could not fetch {} pending nonce, using persisted {}: {:?}", self.network, persisted, e);
+                    Ok(persisted as u64)
+                }
+                None => Err(eyre::eyre!("Could not fetch {} pending nonce: {:?}", self.network, e)),
+            },
+        }
+    }
+
+    async fn persist(&self, next_nonce: u64) -> Result<()> {
+        let address = format!("{:?}", self.address);
+        nonce_repo::save_next_nonce(&self.pool, &self.network, &address, next_nonce as i64).await
+    }
+}
+
+// Whether the node rejected a transaction because its nonce was already used
+pub fn is_nonce_too_low(error: &web3::Error) -> bool {
+    let message = error.to_string().to_lowercase();
+    message.contains("nonce too low") || message.contains("nonce is too low")
+}
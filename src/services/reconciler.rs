@@ -0,0 +1,193 @@
+use chrono::{DateTime, Utc};
+use eyre::Result;
+use sqlx::postgres::PgPool;
+use std::env;
+use std::str::FromStr;
+use web3::types::{Address, U256};
+
+use crate::models::bridge::{BridgeEvent, BridgeEventType};
+use crate::models::reconciliation::{Discrepancy, DiscrepancyKind};
+use crate::models::transfer::BridgeTransfer;
+use crate::repositories::reconciliation as reconciliation_repo;
+use crate::utils::config::BridgeConfig;
+
+/// Outcome of one reconciliation run
+pub struct ReconciliationReport {
+    /// Discrepancies found by the run, all of them are open
+    pub discrepancies: Vec<Discrepancy>,
+    /// Previously open discrepancies the run no longer found
+    pub resolved: u64,
+}
+
+/// Compare the deposits of every chain with the distributions on the chains they are routed to,
+/// record what does not match and resolve what has been fixed since the last run
+pub async fn reconcile(config: &BridgeConfig, pool: &PgPool) -> Result<ReconciliationReport> {
+    let grace_period = chrono::Duration::from_std(config.reconciler.grace_period)?;
+    let unpaid_before = Utc::now() - grace_period;
+
+    let mut discrepancies = Vec::new();
+    for transfer in reconciliation_repo::get_transfers_to_reconcile(pool).await? {
+        discrepancies.extend(check_transfer(config, &transfer, unpaid_before)?);
+    }
+
+    for distribution in reconciliation_repo::get_unpaired_distributions(pool, grace_period.num_seconds()).await? {
+        let sources = source_networks(config, &distribution.network);
+        let deposits = reconciliation_repo::get_deposits_by_nonce(pool, &sources, distribution.nonce).await?;
+        if let Some(discrepancy) = check_unpaired_distribution(&distribution, &sources, &deposits) {
+            discrepancies.push(discrepancy);
+        }
+    }
+
+    let resolved = reconciliation_repo::save_run(pool, &discrepancies).await?;
+    Ok(ReconciliationReport { discrepancies, resolved })
+}
+
+pub async fn start_reconciler(config: BridgeConfig) -> Result<()> {
+    // Initialize database connection
+    let database_url = env::var("DATABASE_URL")
+        .expect("DATABASE_URL must be set in .env file");
+    let pool = PgPool::connect(&database_url).await?;
+    println!("Reconciler connected to PostgreSQL database");
+
+    let interval = config.reconciler.interval;
+    loop {
+        match reconcile(&config, &pool).await {
+            Ok(report) => {
+                if !report.discrepancies.is_empty() || report.resolved > 0 {
+                    println!(
+                        "🧮 Reconciliation: {} open discrepancies, {} resolved",
+                        report.discrepancies.len(), report.resolved
+                    );
+                }
+            }
+            Err(e) => eprintln!("❌ Reconciliation error: {:?}", e),
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
+/// Check a transfer: unpaid if its deposit was made before `unpaid_before` and has no distribution,
+/// otherwise its distribution is checked against the deposit and its route
+pub fn check_transfer(
+    config: &BridgeConfig,
+    transfer: &BridgeTransfer,
+    unpaid_before: DateTime<Utc>,
+) -> Result<Vec<Discrepancy>> {
+    let deposited_at = transfer.deposited_at.unwrap_or(unpaid_before);
+    match transfer.distribution_tx_hash.as_ref() {
+        None if deposited_at <= unpaid_before => Ok(vec![unpaid_deposit(transfer)]),
+        None => Ok(Vec::new()),
+        Some(_) => check_distribution(config, transfer),
+    }
+}
+
+// A confirmed deposit still without a distribution after the grace period
+fn unpaid_deposit(transfer: &BridgeTransfer) -> Discrepancy {
+    let mut details = format!("Deposit of {} to {} is not paid out", transfer.amount, transfer.recipient);
+    match (transfer.target_network.as_ref(), transfer.relay_status.as_ref()) {
+        (Some(target), Some(status)) => details.push_str(&format!(", relay to {} is {}", target, status)),
+        (None, Some(status)) => details.push_str(&format!(", relay is {}", status)),
+        _ => details.push_str(", no relay job"),
+    }
+    if let Some(error) = transfer.relay_error.as_ref() {
+        details.push_str(&format!(": {}", error));
+    }
+
+    Discrepancy::new(
+        DiscrepancyKind::UnpaidDeposit,
+        &transfer.source_network,
+        transfer.nonce,
+        transfer.deposit_tx_hash.clone(),
+        details,
+    )
+}
+
+/// Check that a paired distribution paid the deposit's recipient the amount and token of its route
+pub fn check_distribution(config: &BridgeConfig, transfer: &BridgeTransfer) -> Result<Vec<Discrepancy>> {
+    let mut discrepancies = Vec::new();
+    let discrepancy = |kind, details| Discrepancy::new(
+        kind, &transfer.source_network, transfer.nonce, transfer.distribution_tx_hash.clone(), details
+    );
+
+    let recipient = transfer.distribution_recipient.as_deref().unwrap_or_default();
+    if !recipient.eq_ignore_ascii_case(&transfer.recipient) {
+        discrepancies.push(discrepancy(
+            DiscrepancyKind::RecipientMismatch,
+            format!("Deposit to {} was paid out to {}", transfer.recipient, recipient),
+        ));
+    }
+
+    let deposited = transfer.amount.0;
+    let paid = transfer.distribution_source_amount.unwrap_or_default().0;
+    let route = config.route(&transfer.source_network, Address::from_str(&transfer.source_token)?);
+
+    if transfer.deposit_type == BridgeEventType::Deposit {
+        // Without a route the amount can only be compared as is
+        let expected = route.map_or(Some(deposited), |route| route.convert_amount(deposited));
+        if expected != Some(paid) {
+            discrepancies.push(discrepancy(
+                DiscrepancyKind::AmountMismatch,
+                format!("Deposit of {} should pay out {:?}, {} was paid out", deposited, expected, paid),
+            ));
+        }
+    } else {
+        // The swap rate is applied on-chain, the swap distribution repeats the source amount
+        let target_amount = transfer.distributed_amount.map(U256::from);
+        if paid != deposited || target_amount.is_none_or(|amount| amount.is_zero()) {
+            discrepancies.push(discrepancy(
+                DiscrepancyKind::AmountMismatch,
+                format!(
+                    "Swap deposit of {} was distributed with source amount {} and target amount {:?}",
+                    deposited, paid, target_amount
+                ),
+            ));
+        }
+    }
+
+    let target_token = transfer.target_token.as_deref().map(Address::from_str).transpose()?;
+    if let (Some(route), Some(target_token)) = (route, target_token) {
+        if route.target_token != target_token {
+            discrepancies.push(discrepancy(
+                DiscrepancyKind::TokenMismatch,
+                format!("Route pays out {:?}, {:?} was paid out", route.target_token, target_token),
+            ));
+        }
+    }
+
+    Ok(discrepancies)
+}
+
+/// Networks with a route to the given network, sorted
+pub fn source_networks(config: &BridgeConfig, network: &str) -> Vec<String> {
+    let mut sources: Vec<String> = config.routes.iter()
+        .filter(|route| route.to == network)
+        .map(|route| route.from.clone())
+        .collect();
+    sources.sort();
+    sources.dedup();
+    sources
+}
+
+/// A distribution no relay job accounts for is fine if one of the deposits with its nonce on the
+/// `sources` chains has its recipient, e.g. one relayed before relay jobs were recorded
+pub fn check_unpaired_distribution(
+    distribution: &BridgeEvent,
+    sources: &[String],
+    deposits: &[BridgeEvent],
+) -> Option<Discrepancy> {
+    if deposits.iter().any(|deposit| deposit.to_address.eq_ignore_ascii_case(&distribution.to_address)) {
+        return None;
+    }
+
+    Some(Discrepancy::new(
+        DiscrepancyKind::OrphanDistribution,
+        &distribution.network,
+        distribution.nonce,
+        distribution.tx_hash.clone(),
+        format!(
+            "Distribution of {} to {} has no deposit with nonce {} on {}",
+            distribution.amount, distribution.to_address, distribution.nonce,
+            if sources.is_empty() { "any routed chain".to_string() } else { sources.join(", ") }
+        ),
+    ))
+}
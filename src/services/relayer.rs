@@ -0,0 +1,816 @@
+use web3::{
+    types::{Address, BlockNumber, CallRequest, H256, TransactionParameters, U256, U64},
+    Web3,
+};
+use web3::contract::{Contract, Options};
+use web3::transports::Http;
+use eyre::Result;
+use std::env;
+use web3::ethabi::{Token, Function, Param, ParamType};
+use std::time::Duration;
+use sqlx::postgres::PgPool;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex as StdMutex};
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use std::str::FromStr;
+
+use crate::models::bridge::{BridgeEvent, BridgeEventKind, BridgeEventType};
+use crate::models::numeric::DepositNonce;
+use crate::models::relay::{RelayTransaction, RelayTransactionStatus};
+use crate::repositories::bridge as bridge_repo;
+use crate::repositories::relay as relay_repo;
+use crate::utils::config::{relayer as relayer_config, BridgeConfig, ChainConfig, RouteConfig};
+use crate::services::nonce_manager::{self, NonceManager};
+use crate::utils::abi::load_abi;
+use crate::utils::ethereum;
+use crate::utils::signer::{self, Signer, TxType};
+
+// How often the relayer looks for confirmed deposits to relay
+const RELAYER_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+// Times a relay is re-signed with a fresh nonce after the node reports "nonce too low"
+const NONCE_TOO_LOW_RETRIES: u32 = 1;
+
+// Blocks and reward percentile sampled from eth_feeHistory to price EIP-1559 transactions
+const FEE_HISTORY_BLOCKS: u64 = 10;
+const FEE_HISTORY_PERCENTILE: f64 = 50.0;
+
+// Priority fee used when recent blocks carry no rewards (1 gwei)
+const DEFAULT_PRIORITY_FEE: u64 = 1_000_000_000;
+
+// A chain the relayer sends distribution transactions to
+struct RelayChain {
+    config: ChainConfig,
+    web3: Web3<Http>,
+    nonces: NonceManager,
+}
+
+// Everything needed to send distribution transactions to the configured chains
+struct Relayer {
+    config: BridgeConfig,
+    chains: HashMap<String, RelayChain>,
+    signer: Box<dyn Signer>,
+    distribute_function: Function,
+    swap_distribute_function: Function,
+    erc20: web3::ethabi::Contract,
+    pool: PgPool,
+    // Source network and nonce of the deposits waiting in a relay queue
+    queued: StdMutex<HashSet<(String, DepositNonce)>>,
+}
+
+impl Relayer {
+    fn new(config: BridgeConfig, pool: PgPool) -> Result<Self> {
+        // Unlock the signer of the relayer account once
+        let signer = signer::signer_from_env()?;
+        let relayer_address = signer.address();
+        println!("🔑 Relaying from {:?}", relayer_address);
+
+        // Create distribute function signature
+        #[allow(deprecated)]
+        let distribute_function = Function {
+            name: "distribute".into(),
+            inputs: vec![
+                Param { name: "token".into(), kind: ParamType::Address, internal_type: None },
+                Param { name: "recipient".into(), kind: ParamType::Address, internal_type: None },
+                Param { name: "amount".into(), kind: ParamType::Uint(256), internal_type: None },
+                Param { name: "depositNonce".into(), kind: ParamType::Uint(256), internal_type: None },
+            ],
+            outputs: vec![],
+            constant: false,
+            state_mutability: web3::ethabi::StateMutability::NonPayable,
+        };
+
+        // Create swap distribute function signature
+        #[allow(deprecated)]
+        let swap_distribute_function = Function {
+            name: "swapDistribute".into(),
+            inputs: vec![
+                Param { name: "sourceToken".into(), kind: ParamType::Address, internal_type: None },
+                Param { name: "targetToken".into(), kind: ParamType::Address, internal_type: None },
+                Param { name: "recipient".into(), kind: ParamType::Address, internal_type: None },
+                Param { name: "sourceAmount".into(), kind: ParamType::Uint(256), internal_type: None },
+                Param { name: "nonce".into(), kind: ParamType::Uint(256), internal_type: None },
+            ],
+            outputs: vec![],
+            constant: false,
+            state_mutability: web3::ethabi::StateMutability::NonPayable,
+        };
+
+        // HTTP clients of every chain (for sending transactions)
+        let mut chains = HashMap::new();
+        for chain in &config.chains {
+            let web3 = Web3::new(Http::new(&chain.rpc_url)?);
+            chains.insert(chain.name.clone(), RelayChain {
+                config: chain.clone(),
+                nonces: NonceManager::new(&chain.name, relayer_address, web3.clone(), pool.clone()),
+                web3,
+            });
+        }
+
+        Ok(Self {
+            config,
+            chains,
+            signer,
+            distribute_function,
+            swap_distribute_function,
+            erc20: load_abi("src/abis/erc20.json")?,
+            pool,
+            queued: StdMutex::new(HashSet::new()),
+        })
+    }
+
+    // Route a confirmed deposit is relayed along, None once it has been recorded as unroutable
+    async fn resolve(&self, network: &str, event: &BridgeEvent) -> Result<Option<&RouteConfig>> {
+        let kind = event.kind()?;
+        let source_token = match &kind {
+            BridgeEventKind::Deposit { token, .. } => *token,
+            BridgeEventKind::SwapDeposit { source_token, .. } => *source_token,
+            _ => return Err(eyre::eyre!("{} {} event is not a deposit", network, event.event_type)),
+        };
+        let Some(route) = self.config.route(network, source_token) else {
+            let reason = format!("No route for {} deposits of token {:?}", network, source_token);
+            self.unroutable(network, event, &reason).await?;
+            return Ok(None);
+        };
+
+        let problem = match kind {
+            BridgeEventKind::SwapDeposit { target_token, .. } if target_token != route.target_token => Some(format!(
+                "Swap to token {:?} requested, but {:?} from {} is routed to {:?} on {}",
+                target_token, source_token, network, route.target_token, route.to
+            )),
+            BridgeEventKind::SwapDeposit { .. } => None,
+            _ => {
+                let amount = event.amount.0;
+                match route.convert_amount(amount) {
+                    Some(converted) if !converted.is_zero() || amount.is_zero() => None,
+                    _ => Some(format!("Deposit of {} cannot be paid out with {} decimals", amount, route.target_decimals)),
+                }
+            }
+        };
+
+        match problem {
+            Some(reason) => {
+                self.unroutable(network, event, &reason).await?;
+                Ok(None)
+            }
+            None => Ok(Some(route)),
+        }
+    }
+
+    // Record a deposit that cannot be relayed so it is not picked up again
+    async fn unroutable(&self, network: &str, event: &BridgeEvent, reason: &str) -> Result<()> {
+        let recorded = relay_repo::save_unroutable(
+            &self.pool, network, event.nonce, event.tx_hash.as_deref(), reason
+        ).await?;
+
+        if recorded {
+            println!("🚫 {} deposit with nonce {} is unroutable: {}", network, event.nonce, reason);
+        }
+        Ok(())
+    }
+
+    // Relay a deposit along its route
+    async fn relay(&self, event: &BridgeEvent, route: &RouteConfig) -> Result<()> {
+        match event.event_type {
+            BridgeEventType::Deposit => self.relay_deposit(&event.network, event, route).await,
+            BridgeEventType::SwapDeposit => self.relay_swap_deposit(&event.network, event, route).await,
+            other => Err(eyre::eyre!("Cannot relay a {} event", other)),
+        }
+    }
+
+    // Relay a deposit to its destination chain as a distribution
+    async fn relay_deposit(&self, network: &str, event: &BridgeEvent, route: &RouteConfig) -> Result<()> {
+        let to = Address::from_str(&event.to_address)?;
+        let deposit_amount = event.amount.0;
+        let nonce = event.nonce.0;
+
+        // Now create a distribution transaction on the chain the route leads to
+        let target = self.chain(&route.to)?;
+        let token_address = route.target_token;
+        let amount = route.convert_amount(deposit_amount)
+            .ok_or_else(|| eyre::eyre!("Deposit of {} overflows the target decimals", deposit_amount))?;
+
+        let Some((job_id, waiting)) = self.claim(network, event, &target.config.name).await? else {
+            return Ok(());
+        };
+        if !self.bridge_can_pay(job_id, waiting, target, token_address, amount).await? {
+            return Ok(());
+        }
+
+        println!("\n🔄 Creating distribution transaction on {} network", target.config.name);
+        println!("  Target bridge: {:?}", target.config.bridge_address);
+        println!("  Token:         {:?}", token_address);
+        println!("  Recipient:     {:?}", to);
+        println!("  Deposited:     {}", deposit_amount);
+        println!("  Amount:        {}", amount);
+        println!("  Nonce:         {}", nonce);
+
+        // Create the function call data
+        let call_data = self.distribute_function.encode_input(&[
+            Token::Address(token_address),
+            Token::Address(to),
+            Token::Uint(amount),
+            Token::Uint(nonce),
+        ])?;
+
+        println!("📝 Transaction created and ready to send");
+
+        if let Some(tx_hash) = self.submit(job_id, waiting, target, call_data).await? {
+            println!("🚀 Distribution transaction sent: {:?}", tx_hash);
+        }
+
+        Ok(())
+    }
+
+    // Relay a swap deposit to its destination chain as a swap distribution
+    async fn relay_swap_deposit(&self, network: &str, event: &BridgeEvent, route: &RouteConfig) -> Result<()> {
+        let to = Address::from_str(&event.to_address)?;
+        let source_amount = event.amount.0;
+        let nonce = event.nonce.0;
+
+        // Create swap distribution transaction on the chain the route leads to
+        let target = self.chain(&route.to)?;
+        let source_token_addr = route.source_token;
+        let target_token_addr = route.target_token;
+
+        let Some((job_id, waiting)) = self.claim(network, event, &target.config.name).await? else {
+            return Ok(());
+        };
+        // The swap rate is only known on-chain, so the payout is not checked against the bridge balance;
+        // gas estimation reverts if the bridge cannot pay it
+
+        println!("\n🔄 Creating swap distribution transaction on {} network", target.config.name);
+        println!("  Target bridge:   {:?}", target.config.bridge_address);
+        println!("  Source Token:    {:?}", source_token_addr);
+        println!("  Target Token:    {:?}", target_token_addr);
+        println!("  Recipient:       {:?}", to);
+        println!("  Source Amount:   {}", source_amount);
+        println!("  Nonce:           {}", nonce);
+
+        // Create function call data for swapDistribute
+        let call_data = self.swap_distribute_function.encode_input(&[
+            Token::Address(source_token_addr),
+            Token::Address(target_token_addr),
+            Token::Address(to),
+            Token::Uint(source_amount),
+            Token::Uint(nonce),
+        ])?;
+
+        if let Some(tx_hash) = self.submit(job_id, waiting, target, call_data).await? {
+            println!("🚀 Swap distribution transaction sent: {:?}", tx_hash);
+        }
+
+        Ok(())
+    }
+
+    // Mark a deposit as waiting in a relay queue, false if it already is
+    fn enqueue(&self, event: &BridgeEvent) -> bool {
+        self.queued.lock().unwrap().insert((event.network.clone(), event.nonce))
+    }
+
+    // A queued deposit has been handled and may be dispatched again if it is still unrelayed
+    fn dequeue(&self, event: &BridgeEvent) {
+        self.queued.lock().unwrap().remove(&(event.network.clone(), event.nonce));
+    }
+
+    // Configured chain with the given name
+    fn chain(&self, network: &str) -> Result<&RelayChain> {
+        self.chains.get(network)
+            .ok_or_else(|| eyre::eyre!("Chain {} is not configured", network))
+    }
+
+    // Record the intent to relay before signing, None if another run already claimed the deposit.
+    // Also returns whether the job was already waiting for liquidity.
+    async fn claim(&self, network: &str, event: &BridgeEvent, target_network: &str) -> Result<Option<(i32, bool)>> {
+        let claim = relay_repo::claim_job(
+            &self.pool, network, event.nonce, event.tx_hash.as_deref(), target_network
+        ).await?;
+
+        if claim.is_none() {
+            println!("⏭️ {} deposit with nonce {} was already relayed, skipping", network, event.nonce);
+        }
+        Ok(claim)
+    }
+
+    // Whether the target bridge holds enough tokens for a payout, parks the job otherwise
+    async fn bridge_can_pay(
+        &self,
+        job_id: i32,
+        waiting: bool,
+        target: &RelayChain,
+        token_address: Address,
+        amount: U256,
+    ) -> Result<bool> {
+        let target_address = target.config.bridge_address;
+        let token = Contract::new(target.web3.eth(), token_address, self.erc20.clone());
+        let liquidity: U256 = match token
+            .query("balanceOf", (target_address,), None, Options::default(), None)
+            .await
+        {
+            Ok(liquidity) => liquidity,
+            Err(e) => {
+                let error = format!("Could not read the bridge balance of token {:?}: {:?}", token_address, e);
+                self.retry_later(job_id, &error).await?;
+                return Ok(false);
+            }
+        };
+
+        if liquidity < amount {
+            let reason = format!(
+                "Bridge {:?} holds {} of token {:?}, {} needed", target_address, liquidity, token_address, amount
+            );
+            self.wait_for_liquidity(job_id, waiting, &reason).await?;
+            return Ok(false);
+        }
+        if waiting {
+            println!("▶️ Liquidity is back, resuming relay job {}", job_id);
+        }
+        Ok(true)
+    }
+
+    // Park a job until it can be paid for, logging only when it starts waiting
+    async fn wait_for_liquidity(&self, job_id: i32, waiting: bool, reason: &str) -> Result<()> {
+        if !waiting {
+            println!("⏸️ Relay job {} waits for liquidity: {}", job_id, reason);
+        }
+        relay_repo::mark_insufficient_liquidity(&self.pool, job_id, reason).await
+    }
+
+    // Park a job after a transient RPC error so the next poll relays it again
+    async fn retry_later(&self, job_id: i32, error: &str) -> Result<()> {
+        eprintln!("⚠️ Relay job {} will be retried: {}", job_id, error);
+        relay_repo::mark_retrying(&self.pool, job_id, error).await
+    }
+
+    // Resync the relayer nonces of every chain from the nodes
+    async fn sync_nonces(&self) {
+        for chain in self.chains.values() {
+            match chain.nonces.sync().await {
+                Ok(nonce) => println!("🔢 {} relayer nonce: {}", chain.config.name, nonce),
+                Err(e) => eprintln!("⚠️ Could not sync {} relayer nonce: {:?}", chain.config.name, e),
+            }
+        }
+    }
+
+    // Sign and broadcast a claimed relay, recording the outcome on the job
+    async fn submit(
+        &self,
+        job_id: i32,
+        waiting: bool,
+        target: &RelayChain,
+        call_data: Vec<u8>,
+    ) -> Result<Option<H256>> {
+        let Some(gas) = self.estimate_gas_limit(job_id, target, &call_data).await? else {
+            return Ok(None);
+        };
+
+        let target_network = &target.config.name;
+        let target_web3 = &target.web3;
+        let nonces = &target.nonces;
+        let mut retries = 0;
+
+        loop {
+            // The node may be unreachable for the pending nonce or the fees, the relay is retried then
+            let nonce = match nonces.reserve().await {
+                Ok(nonce) => nonce,
+                Err(e) => {
+                    relay_repo::mark_retrying(&self.pool, job_id, &format!("{:?}", e)).await?;
+                    return Err(e);
+                }
+            };
+            let tx = match self.build_transaction(target, call_data.clone(), gas, nonce).await {
+                Ok(tx) => tx,
+                Err(e) => {
+                    nonces.release(nonce).await?;
+                    relay_repo::mark_retrying(&self.pool, job_id, &format!("{:?}", e)).await?;
+                    return Err(e);
+                }
+            };
+            let raw_tx = match self.signer.sign_transaction(&tx, target.config.chain_id).await {
+                Ok(raw_tx) => raw_tx,
+                Err(e) => {
+                    nonces.release(nonce).await?;
+                    relay_repo::mark_failed(&self.pool, job_id, &format!("{:?}", e)).await?;
+                    return Err(e);
+                }
+            };
+
+            // The relayer must be able to pay for the gas of the transaction
+            let cost = relay_cost(&tx);
+            let balance = match target_web3.eth().balance(self.signer.address(), None).await {
+                Ok(balance) => balance,
+                Err(e) => {
+                    nonces.release(nonce).await?;
+                    let error = format!("Could not read the relayer balance on {}: {:?}", target_network, e);
+                    self.retry_later(job_id, &error).await?;
+                    return Ok(None);
+                }
+            };
+            if balance < cost {
+                nonces.release(nonce).await?;
+                let reason = format!(
+                    "Relayer {:?} holds {} wei on {}, the transaction may cost {}",
+                    self.signer.address(), balance, target_network, cost
+                );
+                self.wait_for_liquidity(job_id, waiting, &reason).await?;
+                return Ok(None);
+            }
+
+            // Record the transaction under the hash it will be mined with before broadcasting it,
+            // so it is tracked even if the node never answers
+            let tx_hash = H256::from(web3::signing::keccak256(&raw_tx));
+            let record = relay_transaction(job_id, target_network, 0, tx_hash, &tx);
+            let record_id = match relay_repo::save_sent_transaction(&self.pool, &record).await {
+                Ok(id) => id,
+                Err(e) => {
+                    nonces.release(nonce).await?;
+                    return Err(e);
+                }
+            };
+
+            // Send the raw transaction
+            match target_web3.eth().send_raw_transaction(raw_tx.into()).await {
+                Ok(_) => return Ok(Some(tx_hash)),
+                Err(e) if nonce_manager::is_nonce_too_low(&e) && retries < NONCE_TOO_LOW_RETRIES => {
+                    eprintln!("⚠️ Nonce {} already used on {}, resyncing", nonce, target_network);
+                    relay_repo::delete_transaction(&self.pool, record_id).await?;
+                    nonces.invalidate().await;
+                    retries += 1;
+                }
+                Err(e) if ethereum::is_rejected(&e) => {
+                    eprintln!("⚠️ {} rejected relay transaction {:?}: {:?}", target_network, tx_hash, e);
+                    relay_repo::delete_transaction(&self.pool, record_id).await?;
+                    nonces.invalidate().await;
+                    relay_repo::mark_failed(&self.pool, job_id, &format!("{:?}", e)).await?;
+                    return Ok(None);
+                }
+                Err(e) => {
+                    // The node may or may not hold the transaction, tracking settles it by receipt or nonce
+                    eprintln!("⚠️ Error sending transaction {:?}, tracking it: {:?}", tx_hash, e);
+                    nonces.invalidate().await;
+                    return Ok(Some(tx_hash));
+                }
+            }
+        }
+    }
+
+    // Gas limit of a relay from eth_estimateGas, None (and the job failed) when it would revert or exceed the cap.
+    // Errors reaching the node leave the job to be retried.
+    async fn estimate_gas_limit(
+        &self,
+        job_id: i32,
+        target: &RelayChain,
+        call_data: &[u8],
+    ) -> Result<Option<U256>> {
+        let request = CallRequest {
+            from: Some(self.signer.address()),
+            to: Some(target.config.bridge_address),
+            data: Some(call_data.to_vec().into()),
+            ..Default::default()
+        };
+
+        let estimate = match target.web3.eth().estimate_gas(request, None).await {
+            Ok(estimate) => estimate,
+            Err(e) => match ethereum::revert_reason(&e) {
+                Some(reason) => {
+                    let error = format!("Gas estimation reverted: {}", reason);
+                    eprintln!("⚠️ {}", error);
+                    relay_repo::mark_failed(&self.pool, job_id, &error).await?;
+                    return Ok(None);
+                }
+                None => {
+                    let error = format!("Gas estimation failed: {:?}", e);
+                    relay_repo::mark_retrying(&self.pool, job_id, &error).await?;
+                    return Err(eyre::eyre!(error));
+                }
+            },
+        };
+
+        match gas_limit(estimate, self.config.relayer.gas_multiplier, self.config.relayer.gas_limit_cap) {
+            Ok(gas) => Ok(Some(gas)),
+            Err(e) => {
+                eprintln!("⚠️ {}", e);
+                relay_repo::mark_failed(&self.pool, job_id, &e.to_string()).await?;
+                Ok(None)
+            }
+        }
+    }
+
+    // Build a priced transaction calling the target bridge
+    async fn build_transaction(
+        &self,
+        target: &RelayChain,
+        call_data: Vec<u8>,
+        gas: U256,
+        nonce: u64,
+    ) -> Result<TransactionParameters> {
+        // Create transaction parameters
+        let mut tx_request = TransactionParameters {
+            nonce: Some(U256::from(nonce)),
+            to: Some(target.config.bridge_address),
+            data: call_data.into(),
+            gas,
+            chain_id: Some(target.config.chain_id),
+            ..Default::default()
+        };
+
+        // Price the transaction the way the target network is configured for
+        match target.config.tx_type {
+            TxType::Eip1559 => {
+                let (max_fee, max_priority_fee) = eip1559_fees(&target.web3).await?;
+                tx_request.transaction_type = Some(U64::from(signer::EIP1559_TX_TYPE));
+                tx_request.max_fee_per_gas = Some(max_fee);
+                tx_request.max_priority_fee_per_gas = Some(max_priority_fee);
+            }
+            TxType::Legacy => {
+                tx_request.gas_price = Some(target.web3.eth().gas_price().await?);
+            }
+        }
+
+        Ok(tx_request)
+    }
+
+    // Follow every unmined relay transaction sent on a network: record receipts and replace the ones
+    // stuck in the mempool
+    async fn track_sent_transactions(&self, network: &str) -> Result<()> {
+        let transactions = relay_repo::get_unresolved_transactions(&self.pool, network).await?;
+
+        for attempts in transactions.chunk_by(|a, b| a.job_id == b.job_id) {
+            if let Err(e) = self.track_job_transactions(attempts).await {
+                eprintln!("❌ Error tracking transactions of relay job {}: {:?}", attempts[0].job_id, e);
+            }
+        }
+
+        Ok(())
+    }
+
+    // Any attempt of a job may be the one that gets mined, since they all share a nonce
+    async fn track_job_transactions(&self, attempts: &[RelayTransaction]) -> Result<()> {
+        let latest = attempts.last().expect("chunks are never empty");
+        let web3 = &self.chain(&latest.network)?.web3;
+
+        for attempt in attempts {
+            let tx_hash = H256::from_str(&attempt.tx_hash)?;
+            let Some(receipt) = web3.eth().transaction_receipt(tx_hash).await? else {
+                continue;
+            };
+            let Some(block_number) = receipt.block_number else {
+                continue;
+            };
+
+            let gas_used = receipt.gas_used.map(|gas| gas.as_u64() as i64);
+            if receipt.status == Some(U64::one()) {
+                println!("✅ Relay transaction {} mined in {} block {}", attempt.tx_hash, attempt.network, block_number);
+                relay_repo::save_receipt(
+                    &self.pool, attempt, RelayTransactionStatus::Succeeded, block_number.as_u64() as i64, gas_used
+                ).await?;
+            } else {
+                eprintln!("❌ Relay transaction {} reverted in {} block {}", attempt.tx_hash, attempt.network, block_number);
+                relay_repo::save_receipt(
+                    &self.pool, attempt, RelayTransactionStatus::Reverted, block_number.as_u64() as i64, gas_used
+                ).await?;
+                relay_repo::mark_failed(
+                    &self.pool, attempt.job_id, &format!("Distribution transaction {} reverted", attempt.tx_hash)
+                ).await?;
+            }
+            return Ok(());
+        }
+
+        if chrono::Utc::now() - latest.sent_at < chrono::Duration::from_std(self.config.relayer.tx_timeout)? {
+            return Ok(());
+        }
+
+        // Nothing we sent was mined but the nonce is used: another transaction took it
+        let mined_nonce = web3.eth().transaction_count(self.signer.address(), Some(BlockNumber::Latest)).await?;
+        if mined_nonce > U256::from(latest.nonce) {
+            eprintln!("⚠️ Nonce {} on {} was used by another transaction, giving up on relay job {}",
+                latest.nonce, latest.network, latest.job_id);
+            relay_repo::drop_transactions(&self.pool, latest.job_id).await?;
+            relay_repo::mark_failed(
+                &self.pool, latest.job_id, &format!("Nonce {} was used by another transaction", latest.nonce)
+            ).await?;
+            return Ok(());
+        }
+
+        if latest.attempt as u64 >= self.config.relayer.max_replacements {
+            println!("⏳ Relay transaction {} still pending after {} replacements", latest.tx_hash, latest.attempt);
+            return Ok(());
+        }
+
+        self.replace_transaction(latest).await
+    }
+
+    // Re-sign a stuck transaction with the same nonce and bumped fees
+    async fn replace_transaction(&self, stuck: &RelayTransaction) -> Result<()> {
+        let chain = self.chain(&stuck.network)?;
+        let web3 = &chain.web3;
+        let chain_id = chain.config.chain_id;
+        let bump = self.config.relayer.gas_bump_percent;
+
+        let mut tx = TransactionParameters {
+            nonce: Some(U256::from(stuck.nonce)),
+            to: Some(Address::from_str(&stuck.to_address)?),
+            data: hex::decode(stuck.call_data.trim_start_matches("0x"))?.into(),
+            gas: U256::from(stuck.gas),
+            chain_id: Some(chain_id),
+            ..Default::default()
+        };
+
+        // Nodes only accept a replacement that pays more on every fee, keep up with the market as well
+        match (&stuck.max_fee_per_gas, &stuck.max_priority_fee_per_gas) {
+            (Some(max_fee), Some(max_priority_fee)) => {
+                let (market_max_fee, market_priority_fee) = eip1559_fees(web3).await?;
+                tx.transaction_type = Some(U64::from(signer::EIP1559_TX_TYPE));
+                tx.max_fee_per_gas = Some(bump_fee(parse_fee(max_fee)?, bump).max(market_max_fee));
+                tx.max_priority_fee_per_gas = Some(bump_fee(parse_fee(max_priority_fee)?, bump).max(market_priority_fee));
+            }
+            _ => {
+                let gas_price = parse_fee(stuck.gas_price.as_deref().unwrap_or("0"))?;
+                tx.gas_price = Some(bump_fee(gas_price, bump).max(web3.eth().gas_price().await?));
+            }
+        }
+
+        let raw_tx = self.signer.sign_transaction(&tx, chain_id).await?;
+        let tx_hash = web3.eth().send_raw_transaction(raw_tx.into()).await?;
+        println!("⛽ Replaced stuck relay transaction {} with {:?} (nonce {})", stuck.tx_hash, tx_hash, stuck.nonce);
+
+        let record = relay_transaction(stuck.job_id, &stuck.network, stuck.attempt + 1, tx_hash, &tx);
+        relay_repo::save_replacement(&self.pool, stuck.id, &record).await?;
+        Ok(())
+    }
+}
+
+// Record of a broadcast transaction, with what is needed to replace it
+fn relay_transaction(job_id: i32, network: &str, attempt: i32, tx_hash: H256, tx: &TransactionParameters) -> RelayTransaction {
+    RelayTransaction {
+        id: 0,
+        job_id,
+        network: network.to_string(),
+        attempt,
+        tx_hash: format!("{:?}", tx_hash),
+        nonce: tx.nonce.unwrap_or_default().as_u64() as i64,
+        to_address: format!("{:?}", tx.to.unwrap_or_default()),
+        call_data: format!("0x{}", hex::encode(&tx.data.0)),
+        gas: tx.gas.as_u64() as i64,
+        gas_price: tx.gas_price.map(|fee| fee.to_string()),
+        max_fee_per_gas: tx.max_fee_per_gas.map(|fee| fee.to_string()),
+        max_priority_fee_per_gas: tx.max_priority_fee_per_gas.map(|fee| fee.to_string()),
+        status: RelayTransactionStatus::Pending,
+        block_number: None,
+        gas_used: None,
+        sent_at: chrono::Utc::now(),
+        mined_at: None,
+    }
+}
+
+// Most a transaction can cost the relayer: its gas limit at its highest fee
+pub fn relay_cost(tx: &TransactionParameters) -> U256 {
+    tx.gas * tx.max_fee_per_gas.or(tx.gas_price).unwrap_or_default()
+}
+
+// Apply the safety multiplier to a gas estimate, capped; refuses estimates above the cap
+pub fn gas_limit(estimate: U256, multiplier: f64, cap: u64) -> Result<U256> {
+    let cap = U256::from(cap);
+    if estimate > cap {
+        return Err(eyre::eyre!("Estimated gas {} exceeds the gas limit cap {}", estimate, cap));
+    }
+    let padded = estimate * U256::from((multiplier * 100.0).round() as u64) / 100;
+    Ok(padded.min(cap))
+}
+
+fn parse_fee(fee: &str) -> Result<U256> {
+    U256::from_dec_str(fee).map_err(|e| eyre::eyre!("Invalid fee {}: {:?}", fee, e))
+}
+
+// Raise a fee by a percentage, rounding up; never by less than nodes accept for a replacement
+pub fn bump_fee(fee: U256, percent: u64) -> U256 {
+    let percent = percent.max(relayer_config::MIN_GAS_BUMP_PERCENT);
+    (fee * (100 + percent) + 99) / 100
+}
+
+// Derive (maxFeePerGas, maxPriorityFeePerGas) from recent blocks.
+// The max fee leaves room for the base fee to double before the transaction stops being includable.
+async fn eip1559_fees(web3: &Web3<Http>) -> Result<(U256, U256)> {
+    let history = web3.eth()
+        .fee_history(U256::from(FEE_HISTORY_BLOCKS), BlockNumber::Latest, Some(vec![FEE_HISTORY_PERCENTILE]))
+        .await?;
+
+    // The last entry is the base fee of the next block
+    let base_fee = history.base_fee_per_gas.last().copied()
+        .ok_or_else(|| eyre::eyre!("eth_feeHistory returned no base fee"))?;
+
+    let mut rewards: Vec<U256> = history.reward.unwrap_or_default()
+        .into_iter()
+        .filter_map(|block_rewards| block_rewards.first().copied())
+        .collect();
+    rewards.sort();
+    let priority_fee = rewards.get(rewards.len() / 2).copied()
+        .unwrap_or_else(|| U256::from(DEFAULT_PRIORITY_FEE));
+
+    Ok((base_fee * 2 + priority_fee, priority_fee))
+}
+
+pub async fn start_relayer(config: BridgeConfig) -> Result<()> {
+    // Initialize database connection
+    let database_url = env::var("DATABASE_URL")
+        .expect("DATABASE_URL must be set in .env file");
+    let pool = PgPool::connect(&database_url).await?;
+    println!("Relayer connected to PostgreSQL database");
+
+    let relayer = Arc::new(Relayer::new(config, pool.clone())?);
+    relayer.sync_nonces().await;
+
+    // Routes may have changed since the last run
+    let released = relay_repo::release_unroutable_jobs(&pool).await?;
+    if released > 0 {
+        println!("🔁 {} unroutable deposits will be routed again", released);
+    }
+
+    // One relay queue per destination chain, so a slow or unreachable chain does not hold up the others
+    let mut queues = HashMap::new();
+    for network in relayer.chains.keys() {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        queues.insert(network.clone(), sender);
+        tokio::spawn(run_relay_queue(relayer.clone(), network.clone(), receiver));
+    }
+
+    loop {
+        if let Err(e) = dispatch_pending_deposits(&relayer, &queues, &pool).await {
+            eprintln!("❌ Relayer error: {:?}", e);
+        }
+        tokio::time::sleep(RELAYER_POLL_INTERVAL).await;
+    }
+}
+
+// Close the jobs whose distribution was indexed, then queue the confirmed deposits without a job
+// for the chain their route leads to
+async fn dispatch_pending_deposits(
+    relayer: &Relayer,
+    queues: &HashMap<String, mpsc::UnboundedSender<(BridgeEvent, RouteConfig)>>,
+    pool: &PgPool,
+) -> Result<()> {
+    let completed = relay_repo::complete_observed_jobs(pool).await?;
+    if completed > 0 {
+        println!("🏁 {} relays completed by an observed distribution", completed);
+    }
+
+    for deposit in bridge_repo::get_unrelayed_deposits(pool).await? {
+        if !relayer.enqueue(&deposit) {
+            continue;
+        }
+
+        let route = match relayer.resolve(&deposit.network, &deposit).await {
+            Ok(Some(route)) => route.clone(),
+            Ok(None) => {
+                relayer.dequeue(&deposit);
+                continue;
+            }
+            Err(e) => {
+                eprintln!("❌ Error routing {} deposit with nonce {}: {:?}", deposit.network, deposit.nonce, e);
+                relayer.dequeue(&deposit);
+                continue;
+            }
+        };
+
+        let target_network = route.to.clone();
+        let queue = queues.get(&target_network)
+            .ok_or_else(|| eyre::eyre!("No relay queue for {}", target_network))?;
+        if queue.send((deposit, route)).is_err() {
+            return Err(eyre::eyre!("Relay queue of {} is closed", target_network));
+        }
+    }
+
+    Ok(())
+}
+
+// Relay the deposits queued for one destination chain one at a time, and follow the transactions
+// sent there between deposits
+async fn run_relay_queue(
+    relayer: Arc<Relayer>,
+    network: String,
+    mut deposits: mpsc::UnboundedReceiver<(BridgeEvent, RouteConfig)>,
+) {
+    let mut last_tracked: Option<Instant> = None;
+
+    loop {
+        if last_tracked.is_none_or(|tracked| tracked.elapsed() >= RELAYER_POLL_INTERVAL) {
+            if let Err(e) = relayer.track_sent_transactions(&network).await {
+                eprintln!("❌ Error tracking {} relay transactions: {:?}", network, e);
+            }
+            last_tracked = Some(Instant::now());
+        }
+
+        match tokio::time::timeout(RELAYER_POLL_INTERVAL, deposits.recv()).await {
+            Ok(Some((deposit, route))) => {
+                if let Err(e) = relayer.relay(&deposit, &route).await {
+                    eprintln!("❌ Error relaying {} deposit with nonce {}: {:?}", deposit.network, deposit.nonce, e);
+                }
+                relayer.dequeue(&deposit);
+            }
+            // The dispatcher is gone, nothing more will be queued
+            Ok(None) => return,
+            Err(_) => {}
+        }
+    }
+}
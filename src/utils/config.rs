@@ -1,28 +1,406 @@
-/// Bridge contract addresses
-pub mod contracts {
-    use web3::types::Address;
-    use std::str::FromStr;
-    
-    /// Bridge contract address on Sepolia
-    pub fn sepolia_bridge_address() -> Address {
-        Address::from_str("0xC57ef84129ee3d73d558c2AE69503060e328d494")
-            .expect("Invalid Sepolia bridge address")
-    }
-    
-    /// Bridge contract address on Holesky
-    pub fn holesky_bridge_address() -> Address {
-        Address::from_str("0x1533600886E59FD9FC1Af1c801C38D4dD9582935")
-            .expect("Invalid Holesky bridge address")
-    }
-}
-
-/// Network configurations
-pub mod networks {
-    /// Get the RPC URL for a specific network
-    pub fn get_rpc_url(network: &str) -> String {
-        match std::env::var(format!("{}_RPC_URL", network.to_uppercase())) {
-            Ok(url) => url,
-            Err(_) => panic!("{}_RPC_URL environment variable not set", network.to_uppercase()),
-        }
-    }
-} 
\ No newline at end of file
+use eyre::{Result, WrapErr};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+use web3::types::{Address, U256};
+
+use crate::utils::signer::TxType;
+
+/// Config file read when none is given on the command line
+pub const DEFAULT_CONFIG_PATH: &str = "config.toml";
+
+/// Confirmations used when none are configured for a chain
+pub const DEFAULT_CONFIRMATIONS: u64 = 12;
+
+/// Interval between two polls of a chain indexed without subscriptions
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(12);
+
+// Most decimals a token amount can be scaled by without 10^decimals overflowing a uint256
+const MAX_TOKEN_DECIMALS: u8 = 77;
+
+/// Chains the bridge is deployed on and the routes deposits are relayed along
+#[derive(Debug, Clone)]
+pub struct BridgeConfig {
+    pub chains: Vec<ChainConfig>,
+    pub routes: Vec<RouteConfig>,
+    /// Read from the `RELAY_*` environment variables
+    pub relayer: relayer::RelayerSettings,
+    /// Read from the `RECONCILE_*` environment variables
+    pub reconciler: reconciler::ReconcilerSettings,
+}
+
+/// One chain the bridge is deployed on
+#[derive(Debug, Clone)]
+pub struct ChainConfig {
+    /// Name the chain is stored under, e.g. the `network` column of bridge events
+    pub name: String,
+    pub chain_id: u64,
+    /// HTTP endpoint used for backfill, receipts and sending relay transactions
+    pub rpc_url: String,
+    /// WebSocket endpoint used for live log and head subscriptions
+    pub ws_url: Option<String>,
+    pub bridge_address: Address,
+    /// Block to index from on the first run, when no checkpoint exists yet
+    pub start_block: Option<u64>,
+    /// Blocks a deposit must be buried under before it is relayed
+    pub confirmations: u64,
+    /// Transaction type the relayer sends on this chain
+    pub tx_type: TxType,
+    /// How new blocks and logs are followed once the indexer is caught up
+    pub indexing: IndexingMode,
+    /// Interval between two polls in `IndexingMode::Poll`
+    pub poll_interval: Duration,
+}
+
+/// How the indexer follows a chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexingMode {
+    /// `eth_subscribe` to logs and new heads over the WebSocket endpoint
+    Subscribe,
+    /// `eth_blockNumber` and `eth_getLogs` over HTTP on an interval
+    Poll,
+}
+
+impl FromStr for IndexingMode {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "subscribe" | "ws" => Ok(IndexingMode::Subscribe),
+            "poll" | "http" => Ok(IndexingMode::Poll),
+            _ => Err(eyre::eyre!("Unknown indexing mode {}", s)),
+        }
+    }
+}
+
+/// Deposits of `source_token` on `from` are paid out in `target_token` on `to`
+#[derive(Debug, Clone)]
+pub struct RouteConfig {
+    pub from: String,
+    pub to: String,
+    pub source_token: Address,
+    pub source_decimals: u8,
+    pub target_token: Address,
+    pub target_decimals: u8,
+}
+
+impl RouteConfig {
+    /// Amount of target tokens paid out for a deposit of `amount` source tokens.
+    /// Scaling down rounds towards zero, None if scaling up overflows a uint256.
+    pub fn convert_amount(&self, amount: U256) -> Option<U256> {
+        if self.target_decimals >= self.source_decimals {
+            let scale = U256::exp10((self.target_decimals - self.source_decimals) as usize);
+            amount.checked_mul(scale)
+        } else {
+            let scale = U256::exp10((self.source_decimals - self.target_decimals) as usize);
+            Some(amount / scale)
+        }
+    }
+}
+
+// Layout of the config file, checked and converted into a BridgeConfig
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawConfig {
+    #[serde(default)]
+    chains: Vec<RawChain>,
+    #[serde(default)]
+    routes: Vec<RawRoute>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawChain {
+    name: String,
+    chain_id: u64,
+    rpc_url: String,
+    ws_url: Option<String>,
+    bridge_address: String,
+    start_block: Option<u64>,
+    confirmations: Option<u64>,
+    tx_type: Option<String>,
+    indexing: Option<String>,
+    poll_interval_secs: Option<u64>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawRoute {
+    from: String,
+    to: String,
+    source_token: String,
+    source_decimals: u8,
+    target_token: String,
+    target_decimals: u8,
+}
+
+impl BridgeConfig {
+    /// Read and validate a TOML config file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .wrap_err_with(|| format!("Could not read config file {}", path.display()))?;
+        Self::parse(&contents)
+            .wrap_err_with(|| format!("Invalid config file {}", path.display()))
+    }
+
+    /// Parse and validate a TOML config, after substituting environment variables
+    pub fn parse(contents: &str) -> Result<Self> {
+        let raw: RawConfig = toml::from_str(&interpolate_env(contents)?)?;
+
+        // Collect every problem so a broken config can be fixed in one go
+        let mut errors = Vec::new();
+        if raw.chains.is_empty() {
+            errors.push("at least one [[chains]] entry is required".to_string());
+        }
+
+        let mut names = HashSet::new();
+        let mut chain_ids = HashSet::new();
+        let mut chains = Vec::new();
+        for chain in raw.chains {
+            let context = format!("chain '{}'", chain.name);
+            if chain.name.is_empty() {
+                errors.push("chain name must not be empty".to_string());
+            }
+            if !names.insert(chain.name.clone()) {
+                errors.push(format!("{}: name is used by another chain", context));
+            }
+            if !chain_ids.insert(chain.chain_id) {
+                errors.push(format!("{}: chain_id {} is used by another chain", context, chain.chain_id));
+            }
+            if !chain.rpc_url.starts_with("http://") && !chain.rpc_url.starts_with("https://") {
+                errors.push(format!("{}: rpc_url '{}' must be an http(s) URL", context, chain.rpc_url));
+            }
+            // An empty ws_url, e.g. from an unset variable, means the chain has no WebSocket endpoint
+            let ws_url = chain.ws_url.filter(|ws_url| !ws_url.is_empty());
+            if let Some(ws_url) = ws_url.as_ref() {
+                if !ws_url.starts_with("ws://") && !ws_url.starts_with("wss://") {
+                    errors.push(format!("{}: ws_url '{}' must be a ws(s) URL", context, ws_url));
+                }
+            }
+            let indexing = match chain.indexing.as_deref() {
+                None | Some("") if ws_url.is_some() => IndexingMode::Subscribe,
+                None | Some("") => IndexingMode::Poll,
+                Some(indexing) => IndexingMode::from_str(indexing).unwrap_or_else(|_| {
+                    errors.push(format!("{}: indexing '{}' must be subscribe or poll", context, indexing));
+                    IndexingMode::Poll
+                }),
+            };
+            if indexing == IndexingMode::Subscribe && ws_url.is_none() {
+                errors.push(format!("{}: indexing 'subscribe' requires a ws_url", context));
+            }
+            if chain.poll_interval_secs == Some(0) {
+                errors.push(format!("{}: poll_interval_secs must be at least 1", context));
+            }
+            let bridge_address = parse_address(&chain.bridge_address, &context, "bridge_address", &mut errors);
+            let tx_type = match chain.tx_type.as_deref() {
+                None | Some("") => TxType::Eip1559,
+                Some(tx_type) => TxType::from_str(tx_type).unwrap_or_else(|_| {
+                    errors.push(format!("{}: tx_type '{}' must be legacy or eip1559", context, tx_type));
+                    TxType::Eip1559
+                }),
+            };
+
+            chains.push(ChainConfig {
+                name: chain.name,
+                chain_id: chain.chain_id,
+                rpc_url: chain.rpc_url,
+                ws_url,
+                bridge_address,
+                start_block: chain.start_block,
+                confirmations: chain.confirmations.unwrap_or(DEFAULT_CONFIRMATIONS),
+                tx_type,
+                indexing,
+                poll_interval: chain.poll_interval_secs.map(Duration::from_secs).unwrap_or(DEFAULT_POLL_INTERVAL),
+            });
+        }
+
+        let mut routes: Vec<RouteConfig> = Vec::new();
+        for route in raw.routes {
+            let context = format!("route '{}' -> '{}'", route.from, route.to);
+            for end in [&route.from, &route.to] {
+                if !names.contains(end) {
+                    errors.push(format!("{}: unknown chain '{}'", context, end));
+                }
+            }
+            if route.from == route.to {
+                errors.push(format!("{}: a route must lead to another chain", context));
+            }
+            let source_token = parse_address(&route.source_token, &context, "source_token", &mut errors);
+            let target_token = parse_address(&route.target_token, &context, "target_token", &mut errors);
+            for (field, decimals) in [("source_decimals", route.source_decimals), ("target_decimals", route.target_decimals)] {
+                if decimals > MAX_TOKEN_DECIMALS {
+                    errors.push(format!("{}: {} {} is above {}", context, field, decimals, MAX_TOKEN_DECIMALS));
+                }
+            }
+
+            let ambiguous = routes.iter()
+                .any(|other| other.from == route.from && other.source_token == source_token);
+            if ambiguous {
+                errors.push(format!("{}: another route already relays {:?} from '{}'", context, source_token, route.from));
+            }
+
+            routes.push(RouteConfig {
+                from: route.from,
+                to: route.to,
+                source_token,
+                source_decimals: route.source_decimals,
+                target_token,
+                target_decimals: route.target_decimals,
+            });
+        }
+
+        let relayer = relayer::RelayerSettings::from_env(&mut errors);
+        let reconciler = reconciler::ReconcilerSettings::from_env(&mut errors);
+
+        if !errors.is_empty() {
+            return Err(eyre::eyre!("{}", errors.join("\n")));
+        }
+        Ok(Self { chains, routes, relayer, reconciler })
+    }
+
+    /// Chain with the given name
+    pub fn chain(&self, name: &str) -> Option<&ChainConfig> {
+        self.chains.iter().find(|chain| chain.name == name)
+    }
+
+    /// Route a deposit of `source_token` made on `from` is relayed along
+    pub fn route(&self, from: &str, source_token: Address) -> Option<&RouteConfig> {
+        self.routes.iter().find(|route| route.from == from && route.source_token == source_token)
+    }
+}
+
+fn parse_address(value: &str, context: &str, field: &str, errors: &mut Vec<String>) -> Address {
+    Address::from_str(value).unwrap_or_else(|_| {
+        errors.push(format!("{}: {} '{}' is not an address", context, field, value));
+        Address::zero()
+    })
+}
+
+/// Substitute `${VAR}` with an environment variable, or `${VAR:-default}` when it is unset or empty.
+/// Comment lines are left as they are.
+pub fn interpolate_env(contents: &str) -> Result<String> {
+    let mut output = String::with_capacity(contents.len());
+
+    for line in contents.split_inclusive('\n') {
+        if line.trim_start().starts_with('#') {
+            output.push_str(line);
+            continue;
+        }
+
+        let mut rest = line;
+        while let Some(start) = rest.find("${") {
+            output.push_str(&rest[..start]);
+            let end = rest[start..].find('}')
+                .ok_or_else(|| eyre::eyre!("Unclosed ${{ in config: {}", rest[start..].trim_end()))?;
+            let expression = &rest[start + 2..start + end];
+
+            let value = match expression.split_once(":-") {
+                Some((name, default)) => match std::env::var(name) {
+                    Ok(value) if !value.is_empty() => value,
+                    _ => default.to_string(),
+                },
+                None => std::env::var(expression)
+                    .map_err(|_| eyre::eyre!("Environment variable {} used in config is not set", expression))?,
+            };
+            output.push_str(&value);
+            rest = &rest[start + end + 1..];
+        }
+        output.push_str(rest);
+    }
+
+    Ok(output)
+}
+
+/// Relayer settings
+pub mod relayer {
+    use std::time::Duration;
+    use super::{env_number, env_var};
+
+    /// Replacement fees must be at least this many percent above the stuck transaction's
+    pub const MIN_GAS_BUMP_PERCENT: u64 = 10;
+
+    #[derive(Debug, Clone)]
+    pub struct RelayerSettings {
+        /// How long a relay transaction may stay unmined before it is replaced with bumped fees
+        pub tx_timeout: Duration,
+        /// Percentage by which fees are raised on each replacement
+        pub gas_bump_percent: u64,
+        /// Replacements sent for one relay before waiting on the last one indefinitely
+        pub max_replacements: u64,
+        /// Factor applied to eth_estimateGas results to get the gas limit of a relay
+        pub gas_multiplier: f64,
+        /// Highest gas limit the relayer will send a transaction with
+        pub gas_limit_cap: u64,
+    }
+
+    impl RelayerSettings {
+        /// Read the `RELAY_*` variables, reporting invalid ones to `errors`
+        pub fn from_env(errors: &mut Vec<String>) -> Self {
+            let gas_bump_percent = env_number("RELAY_GAS_BUMP_PERCENT", 15, errors);
+            if gas_bump_percent < MIN_GAS_BUMP_PERCENT {
+                errors.push(format!("RELAY_GAS_BUMP_PERCENT must be at least {}", MIN_GAS_BUMP_PERCENT));
+            }
+
+            let gas_multiplier = match env_var("RELAY_GAS_MULTIPLIER") {
+                Some(value) => match value.parse::<f64>() {
+                    Ok(multiplier) if multiplier >= 1.0 => multiplier,
+                    _ => {
+                        errors.push(format!("RELAY_GAS_MULTIPLIER '{}' must be a number of at least 1", value));
+                        1.0
+                    }
+                },
+                None => 1.2,
+            };
+
+            Self {
+                tx_timeout: Duration::from_secs(env_number("RELAY_TX_TIMEOUT_SECS", 180, errors)),
+                gas_bump_percent,
+                max_replacements: env_number("RELAY_MAX_REPLACEMENTS", 5, errors),
+                gas_multiplier,
+                gas_limit_cap: env_number("RELAY_GAS_LIMIT_CAP", 1_000_000, errors),
+            }
+        }
+    }
+}
+
+/// Reconciliation settings
+pub mod reconciler {
+    use std::time::Duration;
+    use super::env_number;
+
+    #[derive(Debug, Clone)]
+    pub struct ReconcilerSettings {
+        /// Time between two reconciliation runs
+        pub interval: Duration,
+        /// How long a deposit may go unpaid, or a distribution unmatched, before it is reported
+        pub grace_period: Duration,
+    }
+
+    impl ReconcilerSettings {
+        /// Read the `RECONCILE_*` variables, reporting invalid ones to `errors`
+        pub fn from_env(errors: &mut Vec<String>) -> Self {
+            Self {
+                interval: Duration::from_secs(env_number("RECONCILE_INTERVAL_SECS", 300, errors)),
+                grace_period: Duration::from_secs(env_number("RECONCILE_GRACE_SECS", 3600, errors)),
+            }
+        }
+    }
+}
+
+// Value of an environment variable, None when it is unset or empty
+fn env_var(var: &str) -> Option<String> {
+    std::env::var(var).ok().filter(|value| !value.is_empty())
+}
+
+fn env_number(var: &str, default: u64, errors: &mut Vec<String>) -> u64 {
+    match env_var(var) {
+        Some(value) => value.parse().unwrap_or_else(|_| {
+            errors.push(format!("{} '{}' must be a number", var, value));
+            default
+        }),
+        None => default,
+    }
+}
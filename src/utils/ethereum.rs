@@ -57,4 +57,50 @@ pub async fn get_token_info(contract_address: &str) -> Result<TokenInfo> {
     *TOKEN_CACHE.write().unwrap() = Some(token_info.clone());
     
     Ok(token_info)
-} 
\ No newline at end of file
+} 
+// Selector of Solidity's Error(string), the payload of require and revert with a message
+const ERROR_STRING_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+// Revert reason of a failed call or gas estimation, None when the node did not report a revert
+pub fn revert_reason(error: &web3::Error) -> Option<String> {
+    let web3::Error::Rpc(rpc_error) = error else {
+        return None;
+    };
+
+    let data = rpc_error.data.as_ref().and_then(|data| data.as_str());
+    if let Some(reason) = data.and_then(decode_revert_data) {
+        return Some(reason);
+    }
+
+    // Geth reports reverts with code 3, other nodes only say so in the message
+    if rpc_error.code.code() == 3 || rpc_error.message.to_lowercase().contains("revert") {
+        return Some(match data {
+            Some(data) => format!("{} ({})", rpc_error.message, data),
+            None => rpc_error.message.clone(),
+        });
+    }
+
+    None
+}
+
+// Decode the message of hex revert data encoded as Error(string)
+pub fn decode_revert_data(data: &str) -> Option<String> {
+    let bytes = hex::decode(data.strip_prefix("0x").unwrap_or(data)).ok()?;
+    let payload = bytes.strip_prefix(&ERROR_STRING_SELECTOR[..])?;
+
+    match ethabi::decode(&[ethabi::ParamType::String], payload).ok()?.pop()? {
+        ethabi::Token::String(reason) => Some(reason),
+        _ => None,
+    }
+}
+
+// Whether the node refused a raw transaction, so it will never be mined.
+// Transport errors leave that open, and a node that already holds the transaction did not refuse it.
+pub fn is_rejected(error: &web3::Error) -> bool {
+    let web3::Error::Rpc(rpc_error) = error else {
+        return false;
+    };
+
+    let message = rpc_error.message.to_lowercase();
+    !(message.contains("already known") || message.contains("known transaction"))
+}
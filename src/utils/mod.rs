@@ -1,3 +1,4 @@
 pub mod abi;
 pub mod ethereum; 
-pub mod config;
\ No newline at end of file
+pub mod config;
+pub mod signer;
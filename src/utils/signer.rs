@@ -0,0 +1,406 @@
+use async_trait::async_trait;
+use eyre::Result;
+use rlp::{Rlp, RlpStream};
+use secp256k1::recovery::{RecoverableSignature, RecoveryId};
+use secp256k1::{Message, PublicKey, Secp256k1, SecretKey};
+use serde_json::{json, Value};
+use std::path::Path;
+use std::str::FromStr;
+use web3::transports::Http;
+use web3::types::{AccessList, Address, TransactionParameters, U256, U64};
+use web3::Transport;
+
+/// EIP-2718 type byte of EIP-1559 transactions
+pub const EIP1559_TX_TYPE: u8 = 2;
+
+/// Transaction envelope used for relay transactions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxType {
+    /// Pre-London transaction priced with gasPrice (EIP-155)
+    Legacy,
+    /// Type 2 transaction priced with maxFeePerGas and maxPriorityFeePerGas
+    Eip1559,
+}
+
+impl FromStr for TxType {
+    type Err = eyre::Report;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "legacy" => Ok(TxType::Legacy),
+            "eip1559" | "1559" | "2" => Ok(TxType::Eip1559),
+            other => Err(eyre::eyre!("Unknown transaction type: {}", other)),
+        }
+    }
+}
+
+/// Convert public key to Ethereum address
+pub fn public_key_to_address(pubkey: &PublicKey) -> Address {
+    let pubkey_bytes = pubkey.serialize_uncompressed();
+    let hash = web3::signing::keccak256(&pubkey_bytes[1..]); // Skip the 0x04 prefix
+    Address::from_slice(&hash[12..]) // Last 20 bytes
+}
+
+/// Address controlled by a private key
+pub fn secret_key_to_address(secret_key: &SecretKey) -> Address {
+    let secp = Secp256k1::signing_only();
+    public_key_to_address(&PublicKey::from_secret_key(&secp, secret_key))
+}
+
+/// Parse a hex private key, with or without 0x prefix
+pub fn parse_secret_key(private_key: &str) -> Result<SecretKey> {
+    let private_key_hex = private_key.strip_prefix("0x").unwrap_or(private_key);
+    Ok(SecretKey::from_str(private_key_hex)?)
+}
+
+/// Encode unsigned transaction for signing (EIP-155)
+pub fn encode_unsigned_transaction(tx: &TransactionParameters, chain_id: u64) -> Vec<u8> {
+    let mut stream = RlpStream::new_list(9);
+    append_legacy_fields(&mut stream, tx);
+    stream.append(&chain_id);
+    stream.append(&0u8);
+    stream.append(&0u8);
+    stream.out().to_vec()
+}
+
+/// Encode signed transaction, r and s are 32-byte big-endian scalars
+pub fn encode_signed_transaction(tx: &TransactionParameters, v: u64, r: &[u8], s: &[u8]) -> Vec<u8> {
+    let mut stream = RlpStream::new_list(9);
+    append_legacy_fields(&mut stream, tx);
+    stream.append(&v);
+    // Scalars are RLP integers, so leading zero bytes must be dropped
+    stream.append(&U256::from_big_endian(r));
+    stream.append(&U256::from_big_endian(s));
+    stream.out().to_vec()
+}
+
+fn append_legacy_fields(stream: &mut RlpStream, tx: &TransactionParameters) {
+    stream.append(&tx.nonce.unwrap_or_default());
+    stream.append(&tx.gas_price.unwrap_or_default());
+    stream.append(&tx.gas);
+    stream.append(&tx.to.unwrap_or_default());
+    stream.append(&tx.value);
+    stream.append(&tx.data.0);
+}
+
+/// Encode the EIP-1559 signing payload: 0x02 || rlp([chain_id, nonce, ..., access_list])
+pub fn encode_unsigned_eip1559_transaction(tx: &TransactionParameters, chain_id: u64) -> Vec<u8> {
+    let mut stream = RlpStream::new_list(9);
+    append_eip1559_fields(&mut stream, tx, chain_id);
+    [&[EIP1559_TX_TYPE][..], &stream.out()].concat()
+}
+
+/// Encode a signed EIP-1559 transaction, r and s are 32-byte big-endian scalars
+pub fn encode_signed_eip1559_transaction(
+    tx: &TransactionParameters,
+    chain_id: u64,
+    y_parity: u64,
+    r: &[u8],
+    s: &[u8],
+) -> Vec<u8> {
+    let mut stream = RlpStream::new_list(12);
+    append_eip1559_fields(&mut stream, tx, chain_id);
+    stream.append(&y_parity);
+    stream.append(&U256::from_big_endian(r));
+    stream.append(&U256::from_big_endian(s));
+    [&[EIP1559_TX_TYPE][..], &stream.out()].concat()
+}
+
+fn append_eip1559_fields(stream: &mut RlpStream, tx: &TransactionParameters, chain_id: u64) {
+    stream.append(&chain_id);
+    stream.append(&tx.nonce.unwrap_or_default());
+    stream.append(&tx.max_priority_fee_per_gas.unwrap_or_default());
+    stream.append(&tx.max_fee_per_gas.unwrap_or_default());
+    stream.append(&tx.gas);
+    stream.append(&tx.to.unwrap_or_default());
+    stream.append(&tx.value);
+    stream.append(&tx.data.0);
+    append_access_list(stream, tx.access_list.as_ref());
+}
+
+fn append_access_list(stream: &mut RlpStream, access_list: Option<&AccessList>) {
+    let items = access_list.map(|list| list.as_slice()).unwrap_or_default();
+    stream.begin_list(items.len());
+    for item in items {
+        stream.begin_list(2);
+        stream.append(&item.address);
+        stream.begin_list(item.storage_keys.len());
+        for key in &item.storage_keys {
+            stream.append(key);
+        }
+    }
+}
+
+/// Sign a transaction, returns the raw transaction.
+/// Type 2 transactions are signed as EIP-1559, anything else as legacy EIP-155.
+pub fn sign_transaction(tx: &TransactionParameters, chain_id: u64, secret_key: &SecretKey) -> Result<Vec<u8>> {
+    if tx.transaction_type == Some(U64::from(EIP1559_TX_TYPE)) {
+        sign_eip1559_transaction(tx, chain_id, secret_key)
+    } else {
+        sign_legacy_transaction(tx, chain_id, secret_key)
+    }
+}
+
+/// Sign an EIP-1559 transaction, returns the raw typed transaction
+pub fn sign_eip1559_transaction(tx: &TransactionParameters, chain_id: u64, secret_key: &SecretKey) -> Result<Vec<u8>> {
+    let hash = web3::signing::keccak256(&encode_unsigned_eip1559_transaction(tx, chain_id));
+
+    let (y_parity, sig_bytes) = sign_hash(&hash, secret_key)?;
+    Ok(encode_signed_eip1559_transaction(tx, chain_id, y_parity, &sig_bytes[0..32], &sig_bytes[32..64]))
+}
+
+/// Sign a legacy transaction with EIP-155 replay protection, returns the raw transaction
+pub fn sign_legacy_transaction(tx: &TransactionParameters, chain_id: u64, secret_key: &SecretKey) -> Result<Vec<u8>> {
+    let unsigned_rlp = encode_unsigned_transaction(tx, chain_id);
+    let hash = web3::signing::keccak256(&unsigned_rlp);
+
+    let (rec_id, sig_bytes) = sign_hash(&hash, secret_key)?;
+    let v = chain_id * 2 + 35 + rec_id;
+    Ok(encode_signed_transaction(tx, v, &sig_bytes[0..32], &sig_bytes[32..64]))
+}
+
+/// Recoverable signature of a 32-byte hash, returns the recovery id (0 or 1) and r || s
+pub fn sign_hash(hash: &[u8; 32], secret_key: &SecretKey) -> Result<(u64, [u8; 64])> {
+    let secp = Secp256k1::signing_only();
+    let message = Message::from_slice(hash)?;
+    let signature = secp.sign_recoverable(&message, secret_key);
+    let (rec_id, sig_bytes) = signature.serialize_compact();
+    Ok((rec_id.to_i32() as u64, sig_bytes))
+}
+
+/// Recover the address that signed a 32-byte hash
+pub fn recover_address(hash: &[u8; 32], rec_id: u64, r: &[u8], s: &[u8]) -> Result<Address> {
+    let mut sig_bytes = [0u8; 64];
+    U256::from_big_endian(r).to_big_endian(&mut sig_bytes[0..32]);
+    U256::from_big_endian(s).to_big_endian(&mut sig_bytes[32..64]);
+
+    let secp = Secp256k1::verification_only();
+    let rec_id = RecoveryId::from_i32(rec_id as i32)?;
+    let signature = RecoverableSignature::from_compact(&sig_bytes, rec_id)?;
+    let public_key = secp.recover(&Message::from_slice(hash)?, &signature)?;
+    Ok(public_key_to_address(&public_key))
+}
+
+/// Recover the sender of a raw legacy or EIP-1559 transaction, along with its chain id
+pub fn recover_transaction_sender(raw_tx: &[u8]) -> Result<(Address, u64)> {
+    match raw_tx.first() {
+        Some(&EIP1559_TX_TYPE) => recover_eip1559_sender(&raw_tx[1..]),
+        Some(_) => recover_legacy_sender(raw_tx),
+        None => Err(eyre::eyre!("Empty transaction")),
+    }
+}
+
+fn recover_eip1559_sender(payload: &[u8]) -> Result<(Address, u64)> {
+    let rlp = Rlp::new(payload);
+    if rlp.item_count()? != 12 {
+        return Err(eyre::eyre!("Not an EIP-1559 transaction"));
+    }
+
+    // The signing payload is the first nine fields behind the type byte
+    let mut stream = RlpStream::new_list(9);
+    for index in 0..9 {
+        stream.append_raw(rlp.at(index)?.as_raw(), 1);
+    }
+    let unsigned = [&[EIP1559_TX_TYPE][..], &stream.out()].concat();
+    let hash = web3::signing::keccak256(&unsigned);
+
+    let chain_id: u64 = rlp.val_at(0)?;
+    let y_parity: u64 = rlp.val_at(9)?;
+    let r: Vec<u8> = rlp.val_at(10)?;
+    let s: Vec<u8> = rlp.val_at(11)?;
+    Ok((recover_address(&hash, y_parity, &r, &s)?, chain_id))
+}
+
+fn recover_legacy_sender(raw_tx: &[u8]) -> Result<(Address, u64)> {
+    let rlp = Rlp::new(raw_tx);
+    if rlp.item_count()? != 9 {
+        return Err(eyre::eyre!("Not a legacy transaction"));
+    }
+
+    let v: u64 = rlp.val_at(6)?;
+    if v < 35 {
+        return Err(eyre::eyre!("Transaction is not EIP-155 protected (v = {})", v));
+    }
+    let chain_id = (v - 35) / 2;
+    let rec_id = (v - 35) % 2;
+
+    // Rebuild the signing payload from the first six fields
+    let mut stream = RlpStream::new_list(9);
+    for index in 0..6 {
+        stream.append_raw(rlp.at(index)?.as_raw(), 1);
+    }
+    stream.append(&chain_id);
+    stream.append(&0u8);
+    stream.append(&0u8);
+    let hash = web3::signing::keccak256(&stream.out());
+
+    let r: Vec<u8> = rlp.val_at(7)?;
+    let s: Vec<u8> = rlp.val_at(8)?;
+    Ok((recover_address(&hash, rec_id, &r, &s)?, chain_id))
+}
+
+/// Signs relay transactions on behalf of the relayer account
+#[async_trait]
+pub trait Signer: Send + Sync {
+    /// Account the transactions are sent from
+    fn address(&self) -> Address;
+
+    /// Sign a transaction for a chain, returns the raw transaction
+    async fn sign_transaction(&self, tx: &TransactionParameters, chain_id: u64) -> Result<Vec<u8>>;
+}
+
+/// Signer holding a private key in memory
+pub struct PrivateKeySigner {
+    secret_key: SecretKey,
+    address: Address,
+}
+
+impl PrivateKeySigner {
+    pub fn new(secret_key: SecretKey) -> Self {
+        Self { address: secret_key_to_address(&secret_key), secret_key }
+    }
+
+    /// Signer for a hex private key, with or without 0x prefix
+    pub fn from_hex(private_key: &str) -> Result<Self> {
+        Ok(Self::new(parse_secret_key(private_key)?))
+    }
+}
+
+#[async_trait]
+impl Signer for PrivateKeySigner {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    async fn sign_transaction(&self, tx: &TransactionParameters, chain_id: u64) -> Result<Vec<u8>> {
+        sign_transaction(tx, chain_id, &self.secret_key)
+    }
+}
+
+/// Signer for an encrypted Ethereum JSON keystore (scrypt or pbkdf2), unlocked once with its password
+pub struct KeystoreSigner {
+    key: PrivateKeySigner,
+}
+
+impl KeystoreSigner {
+    pub fn open(path: impl AsRef<Path>, password: &str) -> Result<Self> {
+        let path = path.as_ref();
+        let key_bytes = eth_keystore::decrypt_key(path, password)
+            .map_err(|e| eyre::eyre!("Could not unlock keystore {}: {}", path.display(), e))?;
+        Ok(Self { key: PrivateKeySigner::new(SecretKey::from_slice(&key_bytes)?) })
+    }
+}
+
+#[async_trait]
+impl Signer for KeystoreSigner {
+    fn address(&self) -> Address {
+        self.key.address()
+    }
+
+    async fn sign_transaction(&self, tx: &TransactionParameters, chain_id: u64) -> Result<Vec<u8>> {
+        self.key.sign_transaction(tx, chain_id).await
+    }
+}
+
+/// Signer delegating to a remote service speaking JSON-RPC eth_signTransaction (Clef, Web3Signer, ...)
+pub struct RemoteSigner {
+    transport: Http,
+    address: Address,
+}
+
+impl RemoteSigner {
+    pub fn new(url: &str, address: Address) -> Result<Self> {
+        Ok(Self { transport: Http::new(url)?, address })
+    }
+}
+
+#[async_trait]
+impl Signer for RemoteSigner {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    async fn sign_transaction(&self, tx: &TransactionParameters, chain_id: u64) -> Result<Vec<u8>> {
+        let request = transaction_request(self.address, tx, chain_id);
+        let response = self.transport.execute("eth_signTransaction", vec![request]).await?;
+
+        // Clef and geth answer with { raw, tx }, Web3Signer with the raw transaction alone
+        let raw_hex = response.get("raw").unwrap_or(&response).as_str()
+            .ok_or_else(|| eyre::eyre!("Unexpected eth_signTransaction response: {}", response))?;
+        let raw_tx = hex::decode(raw_hex.strip_prefix("0x").unwrap_or(raw_hex))?;
+
+        // Never broadcast something signed for another account or chain
+        let (sender, signed_chain_id) = recover_transaction_sender(&raw_tx)?;
+        if sender != self.address || signed_chain_id != chain_id {
+            return Err(eyre::eyre!(
+                "Remote signer returned a transaction from {:?} on chain {}, expected {:?} on chain {}",
+                sender, signed_chain_id, self.address, chain_id
+            ));
+        }
+        Ok(raw_tx)
+    }
+}
+
+/// JSON-RPC transaction object of eth_signTransaction
+pub fn transaction_request(from: Address, tx: &TransactionParameters, chain_id: u64) -> Value {
+    let mut request = json!({
+        "from": from,
+        "to": tx.to,
+        "gas": tx.gas,
+        "value": tx.value,
+        "data": tx.data,
+        "nonce": tx.nonce,
+        "chainId": U64::from(chain_id),
+    });
+
+    if tx.transaction_type == Some(U64::from(EIP1559_TX_TYPE)) {
+        request["type"] = json!(U64::from(EIP1559_TX_TYPE));
+        request["maxFeePerGas"] = json!(tx.max_fee_per_gas);
+        request["maxPriorityFeePerGas"] = json!(tx.max_priority_fee_per_gas);
+        request["accessList"] = json!(tx.access_list.clone().unwrap_or_default());
+    } else {
+        request["gasPrice"] = json!(tx.gas_price);
+    }
+    request
+}
+
+/// Relayer signer configured by SIGNER_TYPE: key (default, PRIVATE_KEY), keystore
+/// (KEYSTORE_PATH with KEYSTORE_PASSWORD or KEYSTORE_PASSWORD_FILE) or remote
+/// (REMOTE_SIGNER_URL and REMOTE_SIGNER_ADDRESS)
+pub fn signer_from_env() -> Result<Box<dyn Signer>> {
+    let signer_type = env_value("SIGNER_TYPE").unwrap_or_default();
+    match signer_type.to_lowercase().as_str() {
+        "" | "key" => {
+            let private_key = env_value("PRIVATE_KEY")
+                .ok_or_else(|| eyre::eyre!("PRIVATE_KEY must be set in .env file"))?;
+            Ok(Box::new(PrivateKeySigner::from_hex(&private_key)?))
+        }
+        "keystore" => {
+            let path = env_value("KEYSTORE_PATH")
+                .ok_or_else(|| eyre::eyre!("KEYSTORE_PATH must be set to use a keystore signer"))?;
+            let password = match env_value("KEYSTORE_PASSWORD_FILE") {
+                Some(password_file) => std::fs::read_to_string(&password_file)
+                    .map_err(|e| eyre::eyre!("Could not read KEYSTORE_PASSWORD_FILE {}: {}", password_file, e))?
+                    .trim_end()
+                    .to_string(),
+                None => env_value("KEYSTORE_PASSWORD").ok_or_else(|| eyre::eyre!(
+                    "KEYSTORE_PASSWORD or KEYSTORE_PASSWORD_FILE must be set to use a keystore signer"
+                ))?,
+            };
+            Ok(Box::new(KeystoreSigner::open(path, &password)?))
+        }
+        "remote" => {
+            let url = env_value("REMOTE_SIGNER_URL")
+                .ok_or_else(|| eyre::eyre!("REMOTE_SIGNER_URL must be set to use a remote signer"))?;
+            let address = env_value("REMOTE_SIGNER_ADDRESS")
+                .ok_or_else(|| eyre::eyre!("REMOTE_SIGNER_ADDRESS must be set to use a remote signer"))?;
+            Ok(Box::new(RemoteSigner::new(&url, Address::from_str(&address)?)?))
+        }
+        other => Err(eyre::eyre!("Unknown SIGNER_TYPE: {}", other)),
+    }
+}
+
+// Value of an environment variable, None when it is unset or left empty as in .env.example
+fn env_value(var: &str) -> Option<String> {
+    std::env::var(var).ok().filter(|value| !value.is_empty())
+}
@@ -0,0 +1,73 @@
+use lobster::models::bridge::{BridgeEvent, BridgeEventKind, BridgeEventStatus, BridgeEventType};
+use std::str::FromStr;
+use web3::types::{Address, U256};
+
+fn address(byte: u8) -> Address {
+    Address::repeat_byte(byte)
+}
+
+fn kinds() -> Vec<BridgeEventKind> {
+    vec![
+        BridgeEventKind::Deposit { token: address(1), from: address(2), to: address(3), amount: U256::from(10) },
+        BridgeEventKind::Distribution { token: address(1), to: address(3), amount: U256::from(10) },
+        BridgeEventKind::SwapDeposit {
+            source_token: address(1), target_token: address(4), from: address(2), to: address(3),
+            source_amount: U256::from(10),
+        },
+        BridgeEventKind::SwapDistribution {
+            source_token: address(1), target_token: address(4), to: address(3),
+            source_amount: U256::from(10), target_amount: U256::from(20),
+        },
+    ]
+}
+
+#[test]
+fn converts_every_kind_to_a_row_and_back() {
+    for kind in kinds() {
+        let event = BridgeEvent::new("sepolia", kind.clone(), U256::from(7), Some(100), None);
+        assert_eq!(event.event_type, kind.event_type());
+        assert_eq!(event.kind().unwrap(), kind);
+
+        let status = if event.event_type.is_deposit() { BridgeEventStatus::Pending } else { BridgeEventStatus::Confirmed };
+        assert_eq!(event.status, status);
+    }
+}
+
+#[test]
+fn parses_event_types() {
+    for kind in kinds() {
+        let event_type = kind.event_type();
+        assert_eq!(BridgeEventType::from_str(&event_type.to_string()).unwrap(), event_type);
+    }
+    assert!(BridgeEventType::from_str("Withdrawal").is_err());
+}
+
+#[test]
+fn parses_event_statuses() {
+    for status in [BridgeEventStatus::Pending, BridgeEventStatus::Confirmed, BridgeEventStatus::Historical] {
+        assert_eq!(BridgeEventStatus::from_str(&status.to_string()).unwrap(), status);
+    }
+    assert!(BridgeEventStatus::from_str("Relayed").is_err());
+}
+
+#[test]
+fn rejects_rows_that_do_not_fit_their_type() {
+    let [deposit, distribution, swap_deposit, swap_distribution]: [BridgeEventKind; 4] = kinds().try_into().unwrap();
+    let row = |kind| BridgeEvent::new("sepolia", kind, U256::from(7), Some(100), None);
+
+    let mut event = row(deposit);
+    event.from_address = None;
+    assert!(event.kind().unwrap_err().to_string().contains("from_address is missing"));
+
+    let mut event = row(distribution);
+    event.target_amount = Some(U256::one().into());
+    assert!(event.kind().unwrap_err().to_string().contains("target_amount is not set"));
+
+    let mut event = row(swap_deposit);
+    event.event_type = BridgeEventType::Deposit;
+    assert!(event.kind().is_err());
+
+    let mut event = row(swap_distribution);
+    event.token_address = format!("{:?}", address(1));
+    assert!(event.kind().unwrap_err().to_string().contains("not the target token"));
+}
@@ -0,0 +1,293 @@
+mod common;
+
+use lobster::repositories::checkpoint as checkpoint_repo;
+use lobster::repositories::reorg as reorg_repo;
+use lobster::services::bridge_indexer::NetworkIndexer;
+use lobster::utils::abi::load_abi;
+use lobster::utils::config::{ChainConfig, IndexingMode};
+use lobster::utils::signer::TxType;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use web3::ethabi::{self, Token};
+use web3::transports::Http;
+use web3::types::{Address, Block, Bytes, H256, U256, U64};
+use web3::Web3;
+
+const BRIDGE: &str = "0xC57ef84129ee3d73d558c2AE69503060e328d494";
+
+fn chain(network: &str, rpc_url: &str) -> ChainConfig {
+    ChainConfig {
+        name: network.to_string(),
+        chain_id: 11155111,
+        rpc_url: rpc_url.to_string(),
+        ws_url: None,
+        bridge_address: Address::from_str(BRIDGE).unwrap(),
+        start_block: None,
+        confirmations: 12,
+        tx_type: TxType::Eip1559,
+        indexing: IndexingMode::Poll,
+        poll_interval: Duration::from_secs(12),
+    }
+}
+
+// Hash of a block on one of the forks of the test chain
+fn block_hash(fork: u8, number: u64) -> H256 {
+    let mut hash = H256::from_low_u64_be(number);
+    hash.0[0] = fork;
+    hash
+}
+
+// Deposit log as eth_getLogs returns it, one per block
+fn deposit_log(block_hash: H256, block_number: u64, nonce: u64) -> Value {
+    let abi = load_abi("src/abis/bridge.json").unwrap();
+    let deposit = abi.event("Deposit").unwrap();
+    let topic = |byte: u8| H256::from(Address::repeat_byte(byte));
+
+    json!({
+        "address": BRIDGE,
+        "topics": [deposit.signature(), topic(0x01), topic(0x02), topic(0x03)],
+        "data": Bytes(ethabi::encode(&[Token::Uint(U256::from(1000)), Token::Uint(U256::from(nonce))])),
+        "blockHash": block_hash,
+        "blockNumber": U64::from(block_number),
+        "transactionHash": H256::from_low_u64_be(nonce),
+        "transactionIndex": U64::zero(),
+        "logIndex": U256::zero(),
+        "removed": false,
+    })
+}
+
+// What the stand-in node serves
+#[derive(Default)]
+struct MockChain {
+    logs: Vec<Value>,
+    // Canonical block hashes by number
+    blocks: HashMap<u64, H256>,
+    // eth_getLogs ranges starting at or after this block fail
+    fail_logs_from: Option<u64>,
+}
+
+// Stand-in for a node serving eth_getLogs and eth_getBlockByNumber from a mock chain
+async fn start_chain_node(chain: Arc<Mutex<MockChain>>) -> Web3<Http> {
+    let url = common::start_node(move |request| {
+        let chain = chain.lock().unwrap();
+        let params = &request["params"];
+        let number = |value: &Value| serde_json::from_value::<U64>(value.clone()).unwrap().as_u64();
+
+        match request["method"].as_str().unwrap() {
+            "eth_getLogs" => {
+                let (from_block, to_block) = (number(&params[0]["fromBlock"]), number(&params[0]["toBlock"]));
+                if chain.fail_logs_from.is_some_and(|block| from_block >= block) {
+                    return Err("connection reset".to_string());
+                }
+
+                let logs = chain.logs.iter()
+                    .filter(|log| (from_block..=to_block).contains(&number(&log["blockNumber"])))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                Ok(json!(logs))
+            },
+            "eth_getBlockByNumber" => {
+                let block_number = number(&params[0]);
+                let block = chain.blocks.get(&block_number).map(|hash| Block::<H256> {
+                    hash: Some(*hash),
+                    number: Some(U64::from(block_number)),
+                    ..Default::default()
+                });
+                Ok(json!(block))
+            },
+            method => panic!("Unexpected {} request", method),
+        }
+    })
+    .await;
+
+    Web3::new(Http::new(&url).unwrap())
+}
+
+// Block and status of every stored event of a network
+async fn event_statuses(pool: &PgPool, network: &str) -> Vec<(i64, String)> {
+    sqlx::query_as("SELECT block_number, status FROM bridge_events WHERE network = $1 ORDER BY block_number")
+        .bind(network)
+        .fetch_all(pool)
+        .await
+        .unwrap()
+}
+
+// Blocks of the events of a network orphaned by reorgs
+async fn orphaned_blocks(pool: &PgPool, network: &str) -> Vec<i64> {
+    sqlx::query_scalar("SELECT block_number FROM bridge_events WHERE network = $1 AND orphaned ORDER BY block_number")
+        .bind(network)
+        .fetch_all(pool)
+        .await
+        .unwrap()
+}
+
+// Fork block, depth and orphaned event count of every reorg recorded for a network
+async fn reorgs(pool: &PgPool, network: &str) -> Vec<(i64, i64, i64)> {
+    sqlx::query_as("SELECT fork_block, depth, orphaned_events FROM chain_reorgs WHERE network = $1 ORDER BY id")
+        .bind(network)
+        .fetch_all(pool)
+        .await
+        .unwrap()
+}
+
+async fn delete_network(pool: &PgPool, network: &str) {
+    for table in ["bridge_events", "indexer_checkpoints", "indexed_blocks", "chain_reorgs"] {
+        sqlx::query(&format!("DELETE FROM {} WHERE network = $1", table))
+            .bind(network)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn resumes_an_interrupted_backfill_without_relaying_its_history() {
+    let pool = common::connect_db().await;
+    let network = format!("history-test-{}", std::process::id());
+    let mock = Arc::new(Mutex::new(MockChain {
+        logs: vec![deposit_log(block_hash(0, 1500), 1500, 1), deposit_log(block_hash(0, 4000), 4000, 2)],
+        fail_logs_from: Some(3000),
+        ..Default::default()
+    }));
+    let web3 = start_chain_node(mock.clone()).await;
+    let chain = chain(&network, "http://127.0.0.1:1");
+
+    // The first run backfills from block 1000 to the head at 5000, and dies after the first range
+    let mut indexer = NetworkIndexer::new(&chain, 100, pool.clone()).await.unwrap();
+    assert!(indexer.catch_up(&web3, Some(1000), 5000).await.is_err());
+
+    let checkpoint = checkpoint_repo::get_checkpoint(&pool, &network, &format!("{:?}", chain.bridge_address))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(checkpoint.block_number, 2999);
+    assert_eq!(checkpoint.history_end, Some(5000));
+
+    // The restart resumes from the checkpoint, a deposit was made while it was down
+    {
+        let mut mock = mock.lock().unwrap();
+        mock.logs.push(deposit_log(block_hash(0, 5500), 5500, 3));
+        mock.fail_logs_from = None;
+    }
+    let mut indexer = NetworkIndexer::new(&chain, 100, pool.clone()).await.unwrap();
+    indexer.catch_up(&web3, Some(1000), 6000).await.unwrap();
+
+    // Only the deposits up to the head of the first run are history
+    assert_eq!(event_statuses(&pool, &network).await, vec![
+        (1500, "Historical".to_string()),
+        (4000, "Historical".to_string()),
+        (5500, "Pending".to_string()),
+    ]);
+
+    delete_network(&pool, &network).await;
+}
+
+#[tokio::test]
+async fn keeps_a_checkpoint_behind_a_later_start_block() {
+    let pool = common::connect_db().await;
+    let network = format!("late-start-test-{}", std::process::id());
+    let mock = Arc::new(Mutex::new(MockChain::default()));
+    let web3 = start_chain_node(mock.clone()).await;
+    let chain = chain(&network, "http://127.0.0.1:1");
+
+    // A first run without history goes live at block 100
+    let mut indexer = NetworkIndexer::new(&chain, 100, pool.clone()).await.unwrap();
+    indexer.catch_up(&web3, None, 100).await.unwrap();
+
+    // Restarted after an outage with a start block past the checkpoint
+    mock.lock().unwrap().logs.extend([deposit_log(block_hash(0, 500), 500, 1), deposit_log(block_hash(0, 3500), 3500, 2)]);
+    let mut indexer = NetworkIndexer::new(&chain, 100, pool.clone()).await.unwrap();
+    indexer.catch_up(&web3, Some(3000), 4000).await.unwrap();
+
+    // The blocks before the start are indexed too, and the deposits of the outage are relayed
+    assert_eq!(event_statuses(&pool, &network).await, vec![
+        (500, "Pending".to_string()),
+        (3500, "Pending".to_string()),
+    ]);
+
+    let checkpoint = checkpoint_repo::get_checkpoint(&pool, &network, &format!("{:?}", chain.bridge_address))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(checkpoint.block_number, 4000);
+    assert_eq!(checkpoint.history_end, None);
+
+    delete_network(&pool, &network).await;
+}
+
+// Index a deposit in each of the blocks 10 to 12 and follow their heads
+async fn index_blocks(chain: &ChainConfig, mock: &Mutex<MockChain>, web3: &Web3<Http>, pool: &PgPool) -> NetworkIndexer {
+    {
+        let mut mock = mock.lock().unwrap();
+        mock.blocks = (9..=12).map(|number| (number, block_hash(0, number))).collect();
+        mock.logs = (10..=12).map(|number| deposit_log(block_hash(0, number), number, number)).collect();
+    }
+
+    let mut indexer = NetworkIndexer::new(chain, 100, pool.clone()).await.unwrap();
+    indexer.catch_up(web3, Some(10), 12).await.unwrap();
+    for number in 10..=12 {
+        indexer.handle_new_head(web3, number, block_hash(0, number), block_hash(0, number - 1)).await.unwrap();
+    }
+    assert!(orphaned_blocks(pool, &chain.name).await.is_empty());
+    indexer
+}
+
+#[tokio::test]
+async fn orphans_a_block_replaced_at_the_same_height() {
+    let pool = common::connect_db().await;
+    let network = format!("replaced-block-test-{}", std::process::id());
+    let mock = Arc::new(Mutex::new(MockChain::default()));
+    let web3 = start_chain_node(mock.clone()).await;
+    let chain = chain(&network, "http://127.0.0.1:1");
+    let mut indexer = index_blocks(&chain, &mock, &web3, &pool).await;
+
+    // A sibling of block 12 becomes the head
+    mock.lock().unwrap().blocks.insert(12, block_hash(1, 12));
+    indexer.handle_new_head(&web3, 12, block_hash(1, 12), block_hash(0, 11)).await.unwrap();
+
+    assert_eq!(orphaned_blocks(&pool, &network).await, vec![12]);
+    assert_eq!(reorgs(&pool, &network).await, vec![(12, 1, 1)]);
+
+    // Block 12 is indexed again from the new fork
+    let checkpoint = checkpoint_repo::get_checkpoint(&pool, &network, &format!("{:?}", chain.bridge_address))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!((checkpoint.block_number, checkpoint.log_index), (11, None));
+
+    delete_network(&pool, &network).await;
+}
+
+#[tokio::test]
+async fn walks_back_to_the_fork_on_a_parent_hash_mismatch() {
+    let pool = common::connect_db().await;
+    let network = format!("parent-mismatch-test-{}", std::process::id());
+    let mock = Arc::new(Mutex::new(MockChain::default()));
+    let web3 = start_chain_node(mock.clone()).await;
+    let chain = chain(&network, "http://127.0.0.1:1");
+    let mut indexer = index_blocks(&chain, &mock, &web3, &pool).await;
+
+    // Blocks 11 and 12 are replaced, the new head 13 only reveals it through its parent
+    mock.lock().unwrap().blocks.extend((11..=13).map(|number| (number, block_hash(1, number))));
+    indexer.handle_new_head(&web3, 13, block_hash(1, 13), block_hash(1, 12)).await.unwrap();
+
+    assert_eq!(orphaned_blocks(&pool, &network).await, vec![11, 12]);
+    assert_eq!(reorgs(&pool, &network).await, vec![(11, 2, 2)]);
+
+    let checkpoint = checkpoint_repo::get_checkpoint(&pool, &network, &format!("{:?}", chain.bridge_address))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!((checkpoint.block_number, checkpoint.log_index), (10, None));
+
+    // The replaced blocks are forgotten, the new head is tracked
+    assert!(reorg_repo::get_block(&pool, &network, 12).await.unwrap().is_none());
+    let head = reorg_repo::get_block(&pool, &network, 13).await.unwrap().unwrap();
+    assert_eq!(head.block_hash, format!("{:?}", block_hash(1, 13)));
+
+    delete_network(&pool, &network).await;
+}
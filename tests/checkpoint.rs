@@ -0,0 +1,32 @@
+use lobster::models::checkpoint::IndexerCheckpoint;
+
+const CONTRACT: &str = "0xC57ef84129ee3d73d558c2AE69503060e328d494";
+
+#[test]
+fn covers_logs_up_to_the_checkpointed_log() {
+    let checkpoint = IndexerCheckpoint::new("sepolia", CONTRACT, 100, Some(3));
+
+    // Before the checkpoint block
+    assert!(checkpoint.covers(99, Some(7)));
+    assert!(checkpoint.covers(99, None));
+
+    // At the checkpoint block, only logs up to the checkpointed index
+    assert!(checkpoint.covers(100, Some(2)));
+    assert!(checkpoint.covers(100, Some(3)));
+    assert!(!checkpoint.covers(100, Some(4)));
+    assert!(!checkpoint.covers(100, None));
+
+    // After the checkpoint block
+    assert!(!checkpoint.covers(101, Some(0)));
+    assert!(!checkpoint.covers(101, None));
+}
+
+#[test]
+fn covers_the_whole_block_without_log_index() {
+    let checkpoint = IndexerCheckpoint::new("sepolia", CONTRACT, 100, None);
+
+    assert!(checkpoint.covers(100, Some(0)));
+    assert!(checkpoint.covers(100, Some(500)));
+    assert!(checkpoint.covers(100, None));
+    assert!(!checkpoint.covers(101, Some(0)));
+}
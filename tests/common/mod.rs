@@ -0,0 +1,78 @@
+// Helpers shared by the integration tests, not every test binary uses all of them
+#![allow(dead_code)]
+
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+
+// Connect to the database the sqlx macros were checked against
+pub async fn connect_db() -> PgPool {
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set to run this test");
+    PgPool::connect(&database_url).await.unwrap()
+}
+
+// Stand-in for a JSON-RPC endpoint over HTTP: every request is answered with the result returned by
+// the handler, or with a JSON-RPC error carrying its error message. Returns the URL to connect to.
+pub async fn start_node<F>(handler: F) -> String
+where
+    F: Fn(&Value) -> Result<Value, String> + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handler = Arc::new(handler);
+
+    tokio::spawn(async move {
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            let handler = handler.clone();
+            tokio::spawn(async move {
+                let (reader, mut writer) = stream.into_split();
+                let mut reader = BufReader::new(reader);
+
+                // Serve requests until the client closes the connection
+                loop {
+                    let mut content_length = 0;
+                    let mut line = String::new();
+                    loop {
+                        line.clear();
+                        if reader.read_line(&mut line).await.unwrap() == 0 {
+                            return;
+                        }
+                        if line == "\r\n" {
+                            break;
+                        }
+                        if let Some((name, value)) = line.split_once(':') {
+                            if name.eq_ignore_ascii_case("content-length") {
+                                content_length = value.trim().parse().unwrap();
+                            }
+                        }
+                    }
+                    let mut body = vec![0; content_length];
+                    reader.read_exact(&mut body).await.unwrap();
+
+                    let request: Value = serde_json::from_slice(&body).unwrap();
+                    let response = match handler(&request) {
+                        Ok(result) => json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }),
+                        Err(message) => json!({
+                            "jsonrpc": "2.0",
+                            "id": request["id"],
+                            "error": { "code": -32000, "message": message }
+                        }),
+                    }
+                    .to_string();
+
+                    let head = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n",
+                        response.len()
+                    );
+                    writer.write_all(head.as_bytes()).await.unwrap();
+                    writer.write_all(response.as_bytes()).await.unwrap();
+                }
+            });
+        }
+    });
+
+    url
+}
@@ -0,0 +1,169 @@
+use lobster::utils::config::{self, BridgeConfig, IndexingMode};
+use std::time::Duration;
+use lobster::utils::signer::TxType;
+use std::str::FromStr;
+use web3::types::{Address, U256};
+
+const SEPOLIA_TOKEN: &str = "0x4D77a078a8f698b73b449866ec620DbDc921df39";
+const HOLESKY_TOKEN: &str = "0xFdA8C8E54219577c73C49441E5d86b512ACEfC28";
+
+const VALID_CONFIG: &str = r#"
+[[chains]]
+name = "sepolia"
+chain_id = 11155111
+rpc_url = "https://sepolia.example"
+ws_url = "wss://sepolia.example"
+bridge_address = "0xC57ef84129ee3d73d558c2AE69503060e328d494"
+start_block = 7000000
+confirmations = 3
+
+[[chains]]
+name = "holesky"
+chain_id = 17000
+rpc_url = "https://holesky.example"
+ws_url = "wss://holesky.example"
+bridge_address = "0x1533600886E59FD9FC1Af1c801C38D4dD9582935"
+tx_type = "legacy"
+
+[[routes]]
+from = "sepolia"
+to = "holesky"
+source_token = "0x4D77a078a8f698b73b449866ec620DbDc921df39"
+source_decimals = 6
+target_token = "0xFdA8C8E54219577c73C49441E5d86b512ACEfC28"
+target_decimals = 18
+"#;
+
+#[test]
+fn parses_chains_and_routes() {
+    let config = BridgeConfig::parse(VALID_CONFIG).unwrap();
+
+    let sepolia = config.chain("sepolia").unwrap();
+    assert_eq!(sepolia.chain_id, 11155111);
+    assert_eq!(sepolia.start_block, Some(7000000));
+    assert_eq!(sepolia.confirmations, 3);
+    assert_eq!(sepolia.tx_type, TxType::Eip1559);
+
+    let holesky = config.chain("holesky").unwrap();
+    assert_eq!(holesky.confirmations, config::DEFAULT_CONFIRMATIONS);
+    assert_eq!(holesky.tx_type, TxType::Legacy);
+    assert_eq!(sepolia.indexing, IndexingMode::Subscribe);
+
+    let route = config.route("sepolia", Address::from_str(SEPOLIA_TOKEN).unwrap()).unwrap();
+    assert_eq!(route.to, "holesky");
+    assert_eq!(route.target_token, Address::from_str(HOLESKY_TOKEN).unwrap());
+    assert!(config.route("holesky", Address::from_str(HOLESKY_TOKEN).unwrap()).is_none());
+}
+
+#[test]
+fn converts_amounts_between_decimals() {
+    let config = BridgeConfig::parse(VALID_CONFIG).unwrap();
+    let mut route = config.routes[0].clone();
+
+    // 1.5 tokens with 6 decimals are 1.5 tokens with 18
+    assert_eq!(route.convert_amount(U256::from(1_500_000)), Some(U256::exp10(18) * 3 / 2));
+    assert_eq!(route.convert_amount(U256::MAX), None);
+
+    // Scaling down drops the dust below the target precision
+    std::mem::swap(&mut route.source_decimals, &mut route.target_decimals);
+    assert_eq!(route.convert_amount(U256::exp10(18) * 3 / 2 + 1), Some(U256::from(1_500_000)));
+    assert_eq!(route.convert_amount(U256::from(999)), Some(U256::zero()));
+
+    route.target_decimals = route.source_decimals;
+    assert_eq!(route.convert_amount(U256::MAX), Some(U256::MAX));
+}
+
+#[test]
+fn interpolates_environment_variables() {
+    std::env::set_var("CONFIG_TEST_RPC_URL", "https://rpc.example");
+    std::env::remove_var("CONFIG_TEST_UNSET");
+    std::env::set_var("CONFIG_TEST_EMPTY", "");
+
+    let interpolated = config::interpolate_env(
+        "rpc = \"${CONFIG_TEST_RPC_URL}\"\nconfirmations = ${CONFIG_TEST_UNSET:-12}\nblocks = ${CONFIG_TEST_EMPTY:-5}"
+    ).unwrap();
+    assert_eq!(interpolated, "rpc = \"https://rpc.example\"\nconfirmations = 12\nblocks = 5");
+
+    let commented = "# ${CONFIG_TEST_UNSET} is only read outside comments\n";
+    assert_eq!(config::interpolate_env(commented).unwrap(), commented);
+}
+
+#[test]
+fn reports_missing_environment_variables() {
+    std::env::remove_var("CONFIG_TEST_MISSING");
+    let error = config::interpolate_env("rpc_url = \"${CONFIG_TEST_MISSING}\"").unwrap_err();
+    assert!(error.to_string().contains("CONFIG_TEST_MISSING"), "{}", error);
+}
+
+#[test]
+fn reports_every_invalid_field() {
+    let config = VALID_CONFIG
+        .replace("0x1533600886E59FD9FC1Af1c801C38D4dD9582935", "0x1533")
+        .replace("wss://sepolia.example", "https://sepolia.example")
+        .replace("to = \"holesky\"", "to = \"mainnet\"")
+        .replace("target_decimals = 18", "target_decimals = 78");
+
+    let error = format!("{:#}", BridgeConfig::parse(&config).unwrap_err());
+    assert!(error.contains("chain 'holesky': bridge_address '0x1533' is not an address"), "{}", error);
+    assert!(error.contains("chain 'sepolia': ws_url 'https://sepolia.example' must be a ws(s) URL"), "{}", error);
+    assert!(error.contains("unknown chain 'mainnet'"), "{}", error);
+    assert!(error.contains("target_decimals 78 is above 77"), "{}", error);
+}
+
+#[test]
+fn rejects_duplicate_chains_and_ambiguous_routes() {
+    let config = format!(
+        "{}{}",
+        VALID_CONFIG.replace("name = \"holesky\"", "name = \"sepolia\""),
+        "\n[[routes]]\nfrom = \"sepolia\"\nto = \"holesky\"\nsource_token = \"0x4D77a078a8f698b73b449866ec620DbDc921df39\"\nsource_decimals = 18\ntarget_token = \"0x4D77a078a8f698b73b449866ec620DbDc921df39\"\ntarget_decimals = 18\n"
+    );
+
+    let error = format!("{:#}", BridgeConfig::parse(&config).unwrap_err());
+    assert!(error.contains("name is used by another chain"), "{}", error);
+    assert!(error.contains("another route already relays"), "{}", error);
+}
+
+#[test]
+fn polls_chains_without_websocket_endpoint() {
+    let config = VALID_CONFIG
+        .replace("ws_url = \"wss://holesky.example\"", "ws_url = \"\"\npoll_interval_secs = 4")
+        .replace("confirmations = 3", "confirmations = 3\nindexing = \"poll\"");
+    let config = BridgeConfig::parse(&config).unwrap();
+
+    let holesky = config.chain("holesky").unwrap();
+    assert_eq!(holesky.ws_url, None);
+    assert_eq!(holesky.indexing, IndexingMode::Poll);
+    assert_eq!(holesky.poll_interval, Duration::from_secs(4));
+
+    let sepolia = config.chain("sepolia").unwrap();
+    assert_eq!(sepolia.indexing, IndexingMode::Poll);
+    assert_eq!(sepolia.poll_interval, config::DEFAULT_POLL_INTERVAL);
+}
+
+#[test]
+fn rejects_subscriptions_without_websocket_endpoint() {
+    let config = VALID_CONFIG
+        .replace("ws_url = \"wss://holesky.example\"\n", "")
+        .replace("tx_type = \"legacy\"", "tx_type = \"legacy\"\nindexing = \"subscribe\"");
+
+    let error = format!("{:#}", BridgeConfig::parse(&config).unwrap_err());
+    assert!(error.contains("chain 'holesky': indexing 'subscribe' requires a ws_url"), "{}", error);
+}
+
+#[test]
+fn rejects_unknown_fields() {
+    let config = VALID_CONFIG.replace("confirmations = 3", "confirmation = 3");
+    assert!(BridgeConfig::parse(&config).is_err());
+}
+
+#[test]
+fn loads_the_repository_config() {
+    for network in ["SEPOLIA", "HOLESKY"] {
+        std::env::set_var(format!("{}_RPC_URL", network), "https://rpc.example");
+        std::env::set_var(format!("{}_WS_RPC_URL", network), "wss://ws.example");
+    }
+
+    let config = BridgeConfig::load(config::DEFAULT_CONFIG_PATH).unwrap();
+    assert_eq!(config.chains.len(), 2);
+    assert_eq!(config.routes.len(), 2);
+}
@@ -0,0 +1,58 @@
+use lobster::utils::ethereum;
+
+// Error(string) payload of `revert("Insufficient liquidity")`
+const INSUFFICIENT_LIQUIDITY: &str = "0x08c379a0\
+0000000000000000000000000000000000000000000000000000000000000020\
+0000000000000000000000000000000000000000000000000000000000000016\
+496e73756666696369656e74206c697175696469747900000000000000000000";
+
+fn rpc_error(code: i64, message: &str, data: Option<&str>) -> web3::Error {
+    let error: serde_json::Value = serde_json::json!({
+        "code": code,
+        "message": message,
+        "data": data,
+    });
+    web3::Error::Rpc(serde_json::from_value(error).unwrap())
+}
+
+#[test]
+fn decodes_error_string_revert_data() {
+    assert_eq!(
+        ethereum::decode_revert_data(INSUFFICIENT_LIQUIDITY).as_deref(),
+        Some("Insufficient liquidity")
+    );
+}
+
+#[test]
+fn ignores_custom_error_revert_data() {
+    assert_eq!(ethereum::decode_revert_data("0x1e4fbdf7"), None);
+}
+
+#[test]
+fn reads_revert_reason_from_rpc_error_data() {
+    let error = rpc_error(3, "execution reverted: Insufficient liquidity", Some(INSUFFICIENT_LIQUIDITY));
+    assert_eq!(ethereum::revert_reason(&error).as_deref(), Some("Insufficient liquidity"));
+}
+
+#[test]
+fn falls_back_to_rpc_message_for_reverts_without_reason() {
+    let error = rpc_error(-32000, "execution reverted", None);
+    assert_eq!(ethereum::revert_reason(&error).as_deref(), Some("execution reverted"));
+}
+
+#[test]
+fn transport_errors_are_not_reverts() {
+    let error = rpc_error(-32000, "header not found", None);
+    assert_eq!(ethereum::revert_reason(&error), None);
+    assert_eq!(ethereum::revert_reason(&web3::Error::Unreachable), None);
+}
+
+#[test]
+fn only_node_errors_reject_a_transaction() {
+    assert!(ethereum::is_rejected(&rpc_error(-32000, "insufficient funds for gas * price + value", None)));
+    assert!(ethereum::is_rejected(&rpc_error(-32000, "nonce too low", None)));
+
+    // The node holds the transaction already, or may have it without saying so
+    assert!(!ethereum::is_rejected(&rpc_error(-32000, "already known", None)));
+    assert!(!ethereum::is_rejected(&web3::Error::Unreachable));
+}
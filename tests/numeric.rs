@@ -0,0 +1,48 @@
+use lobster::models::bridge::{BridgeEvent, BridgeEventKind};
+use lobster::models::numeric::{DepositNonce, TokenAmount};
+use sqlx::types::BigDecimal;
+use std::str::FromStr;
+use web3::types::{Address, U256};
+
+#[test]
+fn round_trips_uint256_through_decimals() {
+    for amount in [U256::zero(), U256::exp10(18) * 3 / 2, U256::MAX] {
+        let amount = TokenAmount(amount);
+        assert_eq!(TokenAmount::from_decimal(&amount.to_decimal()).unwrap(), amount);
+    }
+
+    // Whole numbers may come back with trailing zeros folded into the exponent or with a scale
+    let folded = BigDecimal::new(15.into(), -17);
+    assert_eq!(TokenAmount::from_decimal(&folded).unwrap(), TokenAmount(U256::exp10(18) * 3 / 2));
+    let scaled = BigDecimal::from_str("42.000").unwrap();
+    assert_eq!(TokenAmount::from_decimal(&scaled).unwrap(), TokenAmount(U256::from(42)));
+}
+
+#[test]
+fn rejects_amounts_outside_uint256() {
+    assert!(TokenAmount::from_decimal(&BigDecimal::from(-1)).is_err());
+    assert!(TokenAmount::from_decimal(&BigDecimal::from_str("1.5").unwrap()).is_err());
+    let above_max = BigDecimal::from_str(&U256::MAX.to_string()).unwrap() + BigDecimal::from(1);
+    assert!(TokenAmount::from_decimal(&above_max).is_err());
+}
+
+#[test]
+fn serializes_as_decimal_string() {
+    let amount = TokenAmount(U256::MAX);
+    let json = serde_json::to_string(&amount).unwrap();
+    assert_eq!(json, format!("\"{}\"", U256::MAX));
+    assert_eq!(serde_json::from_str::<TokenAmount>(&json).unwrap(), amount);
+}
+
+#[test]
+fn keeps_deposit_nonces_above_u64() {
+    let nonce = U256::from(u64::MAX) * 2 + 1;
+    let kind = BridgeEventKind::Deposit {
+        token: Address::zero(), from: Address::zero(), to: Address::zero(), amount: U256::one()
+    };
+    let event = BridgeEvent::new("sepolia", kind, nonce, Some(1), None);
+    assert_eq!(event.nonce, DepositNonce(nonce));
+    assert_eq!(DepositNonce::from_decimal(&event.nonce.to_decimal()).unwrap(), event.nonce);
+    assert_eq!(DepositNonce::from_str(&nonce.to_string()).unwrap(), event.nonce);
+    assert!(DepositNonce::from_str("-1").is_err());
+}
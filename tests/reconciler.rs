@@ -0,0 +1,240 @@
+use chrono::{Duration, Utc};
+use lobster::models::bridge::{BridgeEvent, BridgeEventKind, BridgeEventType};
+use lobster::models::numeric::DepositNonce;
+use lobster::models::reconciliation::{Discrepancy, DiscrepancyKind};
+use lobster::models::transfer::{BridgeTransfer, TransferStatus};
+use lobster::services::reconciler;
+use lobster::utils::config::BridgeConfig;
+use web3::types::{Address, U256};
+
+const SEPOLIA_TOKEN: &str = "0x4d77a078a8f698b73b449866ec620dbdc921df39";
+const HOLESKY_TOKEN: &str = "0xfda8c8e54219577c73c49441e5d86b512acefc28";
+const RECIPIENT: &str = "0x0303030303030303030303030303030303030303";
+
+const CONFIG: &str = r#"
+[[chains]]
+name = "sepolia"
+chain_id = 11155111
+rpc_url = "https://sepolia.example"
+ws_url = "wss://sepolia.example"
+bridge_address = "0xC57ef84129ee3d73d558c2AE69503060e328d494"
+
+[[chains]]
+name = "holesky"
+chain_id = 17000
+rpc_url = "https://holesky.example"
+ws_url = "wss://holesky.example"
+bridge_address = "0x1533600886E59FD9FC1Af1c801C38D4dD9582935"
+
+[[routes]]
+from = "sepolia"
+to = "holesky"
+source_token = "0x4D77a078a8f698b73b449866ec620DbDc921df39"
+source_decimals = 6
+target_token = "0xFdA8C8E54219577c73C49441E5d86b512ACEfC28"
+target_decimals = 18
+"#;
+
+fn config() -> BridgeConfig {
+    BridgeConfig::parse(CONFIG).unwrap()
+}
+
+// 1.5 tokens deposited on sepolia and not paid out yet
+fn deposit() -> BridgeTransfer {
+    BridgeTransfer {
+        source_network: "sepolia".to_string(),
+        nonce: DepositNonce(U256::from(7)),
+        deposit_type: BridgeEventType::Deposit,
+        source_token: SEPOLIA_TOKEN.to_string(),
+        sender: Some("0x0202020202020202020202020202020202020202".to_string()),
+        recipient: RECIPIENT.to_string(),
+        amount: U256::from(1_500_000).into(),
+        deposit_tx_hash: Some("0xd0".to_string()),
+        deposit_block: Some(100),
+        deposited_at: Some(Utc::now() - Duration::minutes(10)),
+        target_network: Some("holesky".to_string()),
+        relay_status: Some("Sent".to_string()),
+        relay_tx_hash: Some("0xd1".to_string()),
+        relay_error: None,
+        distribution_tx_hash: None,
+        distribution_block: None,
+        target_token: None,
+        distributed_amount: None,
+        distributed_at: None,
+        status: TransferStatus::Relayed,
+        latency_seconds: None,
+        distribution_recipient: None,
+        distribution_source_amount: None,
+        deposit_status: "Confirmed".to_string(),
+    }
+}
+
+// The deposit paid out the converted amount on holesky
+fn paid_deposit() -> BridgeTransfer {
+    let paid: U256 = U256::exp10(18) * 3 / 2;
+    BridgeTransfer {
+        distribution_tx_hash: Some("0xd1".to_string()),
+        distribution_block: Some(200),
+        target_token: Some(HOLESKY_TOKEN.to_string()),
+        distributed_amount: Some(paid.into()),
+        distributed_at: Some(Utc::now()),
+        status: TransferStatus::Completed,
+        distribution_recipient: Some(RECIPIENT.to_string()),
+        distribution_source_amount: Some(paid.into()),
+        ..deposit()
+    }
+}
+
+fn kinds(discrepancies: &[Discrepancy]) -> Vec<DiscrepancyKind> {
+    discrepancies.iter().map(|discrepancy| discrepancy.kind).collect()
+}
+
+#[test]
+fn reports_unpaid_deposits_once_the_grace_period_is_over() {
+    let config = config();
+    let transfer = deposit();
+    let deposited_at = transfer.deposited_at.unwrap();
+
+    // Still within the grace period
+    let found = reconciler::check_transfer(&config, &transfer, deposited_at - Duration::seconds(1)).unwrap();
+    assert!(found.is_empty());
+
+    // The cutoff itself is past the grace period
+    let found = reconciler::check_transfer(&config, &transfer, deposited_at).unwrap();
+    assert_eq!(kinds(&found), vec![DiscrepancyKind::UnpaidDeposit]);
+    assert_eq!(found[0].network, "sepolia");
+    assert_eq!(found[0].tx_hash.as_deref(), Some("0xd0"));
+    assert!(found[0].details.ends_with("relay to holesky is Sent"), "{}", found[0].details);
+
+    // A paid deposit is never unpaid, however old
+    let found = reconciler::check_transfer(&config, &paid_deposit(), Utc::now()).unwrap();
+    assert!(found.is_empty(), "{:?}", found);
+}
+
+#[test]
+fn describes_why_an_unpaid_deposit_was_not_relayed() {
+    let config = config();
+
+    let unrelayed = BridgeTransfer { target_network: None, relay_status: None, relay_tx_hash: None, ..deposit() };
+    let found = reconciler::check_transfer(&config, &unrelayed, Utc::now()).unwrap();
+    assert!(found[0].details.ends_with("no relay job"), "{}", found[0].details);
+
+    // A deposit without a route is recorded as unroutable before a target is picked
+    let unroutable = BridgeTransfer {
+        target_network: None,
+        relay_status: Some("Unroutable".to_string()),
+        relay_error: Some("No route for sepolia deposits".to_string()),
+        ..deposit()
+    };
+    let found = reconciler::check_transfer(&config, &unroutable, Utc::now()).unwrap();
+    assert_eq!(kinds(&found), vec![DiscrepancyKind::UnpaidDeposit]);
+    assert!(found[0].details.ends_with("relay is Unroutable: No route for sepolia deposits"), "{}", found[0].details);
+}
+
+#[test]
+fn reports_recipient_amount_and_token_mismatches() {
+    let config = config();
+    let check = |transfer: &BridgeTransfer| kinds(&reconciler::check_distribution(&config, transfer).unwrap());
+
+    let transfer = BridgeTransfer { distribution_recipient: Some("0x0404040404040404040404040404040404040404".to_string()), ..paid_deposit() };
+    assert_eq!(check(&transfer), vec![DiscrepancyKind::RecipientMismatch]);
+
+    // Paying out the deposited amount unconverted is a mismatch
+    let transfer = BridgeTransfer { distribution_source_amount: Some(U256::from(1_500_000).into()), ..paid_deposit() };
+    assert_eq!(check(&transfer), vec![DiscrepancyKind::AmountMismatch]);
+
+    let transfer = BridgeTransfer { target_token: Some(SEPOLIA_TOKEN.to_string()), ..paid_deposit() };
+    assert_eq!(check(&transfer), vec![DiscrepancyKind::TokenMismatch]);
+
+    // Every mismatch of a distribution is reported, against its distribution transaction
+    let transfer = BridgeTransfer {
+        distribution_recipient: None,
+        distribution_source_amount: None,
+        target_token: Some(SEPOLIA_TOKEN.to_string()),
+        ..paid_deposit()
+    };
+    let found = reconciler::check_distribution(&config, &transfer).unwrap();
+    assert_eq!(
+        kinds(&found),
+        vec![DiscrepancyKind::RecipientMismatch, DiscrepancyKind::AmountMismatch, DiscrepancyKind::TokenMismatch]
+    );
+    assert!(found.iter().all(|discrepancy| discrepancy.tx_hash.as_deref() == Some("0xd1")));
+}
+
+#[test]
+fn checks_that_swap_distributions_repeat_the_source_amount() {
+    let config = config();
+    let check = |transfer: &BridgeTransfer| kinds(&reconciler::check_distribution(&config, transfer).unwrap());
+
+    // The rate is applied on-chain, only the source amount is compared
+    let swap = BridgeTransfer {
+        deposit_type: BridgeEventType::SwapDeposit,
+        distribution_source_amount: Some(U256::from(1_500_000).into()),
+        distributed_amount: Some(U256::from(42).into()),
+        ..paid_deposit()
+    };
+    assert!(check(&swap).is_empty());
+
+    let transfer = BridgeTransfer { distribution_source_amount: Some(U256::from(1_000_000).into()), ..swap.clone() };
+    assert_eq!(check(&transfer), vec![DiscrepancyKind::AmountMismatch]);
+
+    // A swap that paid nothing out did not pay out the deposit
+    let transfer = BridgeTransfer { distributed_amount: Some(U256::zero().into()), ..swap.clone() };
+    assert_eq!(check(&transfer), vec![DiscrepancyKind::AmountMismatch]);
+    let transfer = BridgeTransfer { distributed_amount: None, ..swap };
+    assert_eq!(check(&transfer), vec![DiscrepancyKind::AmountMismatch]);
+}
+
+#[test]
+fn compares_deposits_without_a_route_as_is() {
+    let config = config();
+    let check = |transfer: &BridgeTransfer| kinds(&reconciler::check_distribution(&config, transfer).unwrap());
+
+    // Neither the amount is converted nor the token checked without a route
+    let unroutable = BridgeTransfer {
+        source_token: "0x0505050505050505050505050505050505050505".to_string(),
+        distribution_source_amount: Some(U256::from(1_500_000).into()),
+        ..paid_deposit()
+    };
+    assert!(check(&unroutable).is_empty());
+
+    let converted: U256 = U256::exp10(18) * 3 / 2;
+    let transfer = BridgeTransfer { distribution_source_amount: Some(converted.into()), ..unroutable };
+    assert_eq!(check(&transfer), vec![DiscrepancyKind::AmountMismatch]);
+}
+
+#[test]
+fn accepts_unpaired_distributions_with_a_deposit_on_a_routed_chain() {
+    let config = config();
+    assert_eq!(reconciler::source_networks(&config, "holesky"), vec!["sepolia".to_string()]);
+    assert!(reconciler::source_networks(&config, "sepolia").is_empty());
+
+    let recipient: Address = RECIPIENT.parse().unwrap();
+    let distribution = BridgeEvent::new(
+        "holesky",
+        BridgeEventKind::Distribution { token: HOLESKY_TOKEN.parse().unwrap(), to: recipient, amount: U256::from(10) },
+        U256::from(7),
+        Some(200),
+        Some("0xd1".to_string()),
+    );
+    let deposit = |to: Address| BridgeEvent::new(
+        "sepolia",
+        BridgeEventKind::Deposit { token: SEPOLIA_TOKEN.parse().unwrap(), from: Address::repeat_byte(0x02), to, amount: U256::from(10) },
+        U256::from(7),
+        Some(100),
+        Some("0xd0".to_string()),
+    );
+    let sources = reconciler::source_networks(&config, "holesky");
+
+    // A deposit with the nonce and the recipient accounts for the distribution
+    assert!(reconciler::check_unpaired_distribution(&distribution, &sources, &[deposit(recipient)]).is_none());
+
+    // A deposit with the nonce for someone else does not
+    let orphan = reconciler::check_unpaired_distribution(&distribution, &sources, &[deposit(Address::repeat_byte(0x04))]).unwrap();
+    assert_eq!(orphan.kind, DiscrepancyKind::OrphanDistribution);
+    assert_eq!((orphan.network.as_str(), orphan.tx_hash.as_deref()), ("holesky", Some("0xd1")));
+    assert!(orphan.details.ends_with("with nonce 7 on sepolia"), "{}", orphan.details);
+
+    let orphan = reconciler::check_unpaired_distribution(&distribution, &[], &[]).unwrap();
+    assert!(orphan.details.ends_with("on any routed chain"), "{}", orphan.details);
+}
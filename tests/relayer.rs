@@ -0,0 +1,93 @@
+mod common;
+
+use lobster::services::nonce_manager::NonceManager;
+use lobster::services::relayer::{bump_fee, gas_limit, relay_cost};
+use serde_json::json;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use web3::transports::Http;
+use web3::types::{Address, TransactionParameters, U256};
+use web3::Web3;
+
+#[test]
+fn pads_gas_estimates_up_to_the_cap() {
+    assert_eq!(gas_limit(U256::from(100_000), 1.2, 1_000_000).unwrap(), U256::from(120_000));
+    assert_eq!(gas_limit(U256::from(100_000), 1.0, 1_000_000).unwrap(), U256::from(100_000));
+
+    // Padding stops at the cap, an estimate above it is refused
+    assert_eq!(gas_limit(U256::from(900_000), 1.2, 1_000_000).unwrap(), U256::from(1_000_000));
+    assert_eq!(gas_limit(U256::from(1_000_000), 1.2, 1_000_000).unwrap(), U256::from(1_000_000));
+    let error = gas_limit(U256::from(1_000_001), 1.2, 1_000_000).unwrap_err();
+    assert!(error.to_string().contains("exceeds the gas limit cap"), "{}", error);
+}
+
+#[test]
+fn bumps_fees_by_at_least_ten_percent() {
+    assert_eq!(bump_fee(U256::from(100), 15), U256::from(115));
+    // Rounded up, so small fees still rise
+    assert_eq!(bump_fee(U256::from(1), 15), U256::from(2));
+    assert_eq!(bump_fee(U256::from(1_000_000_001), 10), U256::from(1_100_000_002));
+    // Below the minimum nodes accept, the bump is raised to 10%
+    assert_eq!(bump_fee(U256::from(100), 5), U256::from(110));
+    assert_eq!(bump_fee(U256::zero(), 15), U256::zero());
+}
+
+#[test]
+fn prices_relays_at_their_highest_fee() {
+    let eip1559 = TransactionParameters {
+        gas: U256::from(120_000),
+        max_fee_per_gas: Some(U256::from(30)),
+        max_priority_fee_per_gas: Some(U256::from(2)),
+        ..Default::default()
+    };
+    assert_eq!(relay_cost(&eip1559), U256::from(3_600_000));
+
+    let legacy = TransactionParameters { gas: U256::from(21_000), gas_price: Some(U256::from(10)), ..Default::default() };
+    assert_eq!(relay_cost(&legacy), U256::from(210_000));
+}
+
+// Stand-in for a node: answers eth_getTransactionCount with the shared pending count
+async fn start_nonce_node(pending: Arc<AtomicU64>) -> String {
+    common::start_node(move |request| {
+        assert_eq!(request["method"], "eth_getTransactionCount");
+        Ok(json!(U256::from(pending.load(Ordering::SeqCst))))
+    })
+    .await
+}
+
+#[tokio::test]
+async fn reuses_released_nonces_and_resyncs_after_gaps() {
+    let pool = common::connect_db().await;
+    let pending = Arc::new(AtomicU64::new(7));
+    let web3 = Web3::new(Http::new(&start_nonce_node(pending.clone()).await).unwrap());
+
+    // A network of its own, so runs do not see each other's persisted nonces
+    let network = format!("nonce-test-{}", std::process::id());
+    let nonces = NonceManager::new(&network, Address::repeat_byte(0x11), web3, pool.clone());
+
+    // Reservations are handed out locally, back to back
+    assert_eq!(nonces.reserve().await.unwrap(), 7);
+    assert_eq!(nonces.reserve().await.unwrap(), 8);
+
+    // Releasing the latest nonce hands it out again
+    nonces.release(8).await.unwrap();
+    assert_eq!(nonces.reserve().await.unwrap(), 8);
+
+    // Releasing an earlier nonce leaves a gap, the next reservation asks the node
+    assert_eq!(nonces.reserve().await.unwrap(), 9);
+    pending.store(8, Ordering::SeqCst);
+    nonces.release(8).await.unwrap();
+    assert_eq!(nonces.reserve().await.unwrap(), 8);
+
+    // After an invalidation the node's count wins over the local one
+    pending.store(12, Ordering::SeqCst);
+    nonces.invalidate().await;
+    assert_eq!(nonces.reserve().await.unwrap(), 12);
+    assert_eq!(nonces.reserve().await.unwrap(), 13);
+
+    sqlx::query("DELETE FROM relayer_nonces WHERE network = $1")
+        .bind(&network)
+        .execute(&pool)
+        .await
+        .unwrap();
+}
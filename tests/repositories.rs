@@ -0,0 +1,144 @@
+mod common;
+
+use lobster::models::bridge::{BridgeEvent, BridgeEventKind, BridgeEventStatus};
+use lobster::models::numeric::DepositNonce;
+use lobster::models::relay::{RelayTransaction, RelayTransactionStatus};
+use lobster::repositories::bridge as bridge_repo;
+use lobster::repositories::relay as relay_repo;
+use sqlx::PgPool;
+use web3::types::{Address, U256};
+
+fn deposit(network: &str, nonce: u64) -> BridgeEvent {
+    let kind = BridgeEventKind::Deposit {
+        token: Address::repeat_byte(0x01),
+        from: Address::repeat_byte(0x02),
+        to: Address::repeat_byte(0x03),
+        amount: U256::from(1000),
+    };
+    let mut event = BridgeEvent::new(network, kind, U256::from(nonce), Some(100), Some(format!("0x{:064x}", nonce)));
+    event.block_hash = Some(format!("0x{:064x}", 100));
+    event.log_index = Some(0);
+    event
+}
+
+async fn delete_events(pool: &PgPool, network: &str) {
+    sqlx::query("DELETE FROM bridge_events WHERE network = $1")
+        .bind(network)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn redelivered_events_are_updated_and_made_canonical_again() {
+    let pool = common::connect_db().await;
+    let network = format!("upsert-test-{}", std::process::id());
+    let mut event = deposit(&network, 1);
+
+    assert!(bridge_repo::save_bridge_event(&pool, &event).await.unwrap());
+    let id: i32 = sqlx::query_scalar("SELECT id FROM bridge_events WHERE network = $1")
+        .bind(&network)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    bridge_repo::set_event_status(&pool, id, BridgeEventStatus::Confirmed).await.unwrap();
+
+    // A reorg orphans the event, then the log is delivered again from its new block
+    let orphaned = bridge_repo::orphan_events_in_blocks(&pool, &network, &[event.block_hash.clone().unwrap()]).await.unwrap();
+    assert_eq!(orphaned, 1);
+    event.block_number = Some(101);
+    event.block_hash = Some(format!("0x{:064x}", 101));
+    assert!(!bridge_repo::save_bridge_event(&pool, &event).await.unwrap());
+
+    // Still one row, moved to the new block, canonical and as confirmed as before
+    let rows: Vec<(i32, i64, String, bool, String)> =
+        sqlx::query_as("SELECT id, block_number, block_hash, orphaned, status FROM bridge_events WHERE network = $1")
+        .bind(&network)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(rows, vec![(id, 101, format!("0x{:064x}", 101), false, "Confirmed".to_string())]);
+
+    delete_events(&pool, &network).await;
+}
+
+#[tokio::test]
+async fn claims_a_deposit_once_and_takes_back_waiting_jobs() {
+    let pool = common::connect_db().await;
+    let network = format!("claim-test-{}", std::process::id());
+    let nonce = DepositNonce(U256::from(1));
+    let claim = || relay_repo::claim_job(&pool, &network, nonce, Some("0x01"), "holesky");
+
+    let (id, waiting) = claim().await.unwrap().unwrap();
+    assert!(!waiting);
+
+    // A claimed deposit is not handed out again
+    assert_eq!(claim().await.unwrap(), None);
+
+    // Jobs parked for liquidity or after an RPC error are claimed again, as waiting
+    relay_repo::mark_insufficient_liquidity(&pool, id, "bridge balance too low").await.unwrap();
+    assert_eq!(claim().await.unwrap(), Some((id, true)));
+    relay_repo::mark_retrying(&pool, id, "connection reset").await.unwrap();
+    assert_eq!(claim().await.unwrap(), Some((id, true)));
+
+    // Neither are sent or failed jobs
+    relay_repo::mark_sent(&pool, id, "0x02").await.unwrap();
+    assert_eq!(claim().await.unwrap(), None);
+    relay_repo::mark_failed(&pool, id, "execution reverted").await.unwrap();
+    assert_eq!(claim().await.unwrap(), None);
+
+    sqlx::query("DELETE FROM relay_jobs WHERE source_network = $1")
+        .bind(&network)
+        .execute(&pool)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn records_a_transaction_before_it_is_broadcast() {
+    let pool = common::connect_db().await;
+    let network = format!("sent-test-{}", std::process::id());
+    let nonce = DepositNonce(U256::from(1));
+    let (job_id, _) = relay_repo::claim_job(&pool, &network, nonce, Some("0x01"), &network).await.unwrap().unwrap();
+
+    let tx = RelayTransaction {
+        id: 0,
+        job_id,
+        network: network.clone(),
+        attempt: 0,
+        tx_hash: format!("0x{:064x}", std::process::id()),
+        nonce: 3,
+        to_address: format!("{:?}", Address::repeat_byte(0x04)),
+        call_data: "0x".to_string(),
+        gas: 100000,
+        gas_price: Some("1000000000".to_string()),
+        max_fee_per_gas: None,
+        max_priority_fee_per_gas: None,
+        status: RelayTransactionStatus::Pending,
+        block_number: None,
+        gas_used: None,
+        sent_at: chrono::Utc::now(),
+        mined_at: None,
+    };
+
+    // The job points at the transaction and tracking follows it
+    let id = relay_repo::save_sent_transaction(&pool, &tx).await.unwrap();
+    let job: (String, Option<String>) = sqlx::query_as("SELECT status, tx_hash FROM relay_jobs WHERE id = $1")
+        .bind(job_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(job, ("Sent".to_string(), Some(tx.tx_hash.clone())));
+    let unresolved = relay_repo::get_unresolved_transactions(&pool, &network).await.unwrap();
+    assert_eq!(unresolved.iter().map(|tx| tx.id).collect::<Vec<_>>(), vec![id]);
+
+    // A transaction the node refused is forgotten
+    relay_repo::delete_transaction(&pool, id).await.unwrap();
+    assert!(relay_repo::get_unresolved_transactions(&pool, &network).await.unwrap().is_empty());
+
+    sqlx::query("DELETE FROM relay_jobs WHERE source_network = $1")
+        .bind(&network)
+        .execute(&pool)
+        .await
+        .unwrap();
+}
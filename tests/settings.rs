@@ -0,0 +1,26 @@
+use lobster::utils::config::relayer::RelayerSettings;
+use lobster::utils::config::reconciler::ReconcilerSettings;
+use std::time::Duration;
+
+// Kept in its own test binary, the variables it sets would leak into other config tests
+#[test]
+fn reports_invalid_settings_instead_of_panicking() {
+    std::env::set_var("RELAY_GAS_BUMP_PERCENT", "5");
+    std::env::set_var("RELAY_GAS_MULTIPLIER", "0.5");
+    std::env::set_var("RELAY_TX_TIMEOUT_SECS", "");
+    std::env::set_var("RELAY_GAS_LIMIT_CAP", "lots");
+    std::env::set_var("RECONCILE_INTERVAL_SECS", "60");
+
+    let mut errors = Vec::new();
+    let relayer = RelayerSettings::from_env(&mut errors);
+    let reconciler = ReconcilerSettings::from_env(&mut errors);
+
+    assert_eq!(errors, vec![
+        "RELAY_GAS_BUMP_PERCENT must be at least 10".to_string(),
+        "RELAY_GAS_MULTIPLIER '0.5' must be a number of at least 1".to_string(),
+        "RELAY_GAS_LIMIT_CAP 'lots' must be a number".to_string(),
+    ]);
+    // Empty variables fall back to their defaults
+    assert_eq!(relayer.tx_timeout, Duration::from_secs(180));
+    assert_eq!(reconciler.interval, Duration::from_secs(60));
+}
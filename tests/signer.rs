@@ -0,0 +1,174 @@
+use lobster::utils::signer;
+use rlp::Rlp;
+use std::str::FromStr;
+use web3::signing::{Key, Signature, SigningError};
+use web3::transports::Http;
+use web3::types::{AccessListItem, Address, TransactionParameters, H256, U256, U64};
+use web3::Web3;
+
+// Private key and transaction from the EIP-155 specification
+const EIP155_PRIVATE_KEY: &str = "0x4646464646464646464646464646464646464646464646464646464646464646";
+const EIP155_ADDRESS: &str = "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F";
+const EIP155_SIGNED_TX: &str = "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83";
+
+fn eip155_transaction() -> TransactionParameters {
+    TransactionParameters {
+        nonce: Some(U256::from(9)),
+        gas_price: Some(U256::from(20_000_000_000u64)),
+        gas: U256::from(21000),
+        to: Some(Address::from_str("0x3535353535353535353535353535353535353535").unwrap()),
+        value: U256::from(1_000_000_000_000_000_000u64),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn derives_address_from_private_key() {
+    let secret_key = signer::parse_secret_key(EIP155_PRIVATE_KEY).unwrap();
+    assert_eq!(
+        signer::secret_key_to_address(&secret_key),
+        Address::from_str(EIP155_ADDRESS).unwrap()
+    );
+}
+
+#[test]
+fn signs_eip155_reference_transaction() {
+    let secret_key = signer::parse_secret_key(EIP155_PRIVATE_KEY).unwrap();
+    let raw_tx = signer::sign_transaction(&eip155_transaction(), 1, &secret_key).unwrap();
+    assert_eq!(hex::encode(raw_tx), EIP155_SIGNED_TX);
+}
+
+#[test]
+fn recovers_sender_of_reference_transaction() {
+    let raw_tx = hex::decode(EIP155_SIGNED_TX).unwrap();
+    let (sender, chain_id) = signer::recover_transaction_sender(&raw_tx).unwrap();
+    assert_eq!(sender, Address::from_str(EIP155_ADDRESS).unwrap());
+    assert_eq!(chain_id, 1);
+}
+
+#[test]
+fn recovers_sender_for_both_recovery_ids() {
+    let secret_key = signer::parse_secret_key(EIP155_PRIVATE_KEY).unwrap();
+    let expected = Address::from_str(EIP155_ADDRESS).unwrap();
+    let chain_id = 11155111;
+
+    let mut seen_v = std::collections::HashSet::new();
+    for nonce in 0..32u64 {
+        let tx = TransactionParameters {
+            nonce: Some(U256::from(nonce)),
+            ..eip155_transaction()
+        };
+        let raw_tx = signer::sign_transaction(&tx, chain_id, &secret_key).unwrap();
+
+        let v: u64 = Rlp::new(&raw_tx).val_at(6).unwrap();
+        seen_v.insert(v);
+
+        let (sender, recovered_chain_id) = signer::recover_transaction_sender(&raw_tx).unwrap();
+        assert_eq!(sender, expected, "wrong sender for nonce {}", nonce);
+        assert_eq!(recovered_chain_id, chain_id);
+    }
+
+    // Both parities must show up, a hard-coded recovery id would only produce one
+    assert_eq!(seen_v.len(), 2);
+}
+
+fn eip1559_transaction() -> TransactionParameters {
+    TransactionParameters {
+        nonce: Some(U256::from(3)),
+        gas: U256::from(300000),
+        to: Some(Address::from_str("0x1533600886E59FD9FC1Af1c801C38D4dD9582935").unwrap()),
+        data: hex::decode("a9059cbb0000000000000000000000003535353535353535353535353535353535353535").unwrap().into(),
+        chain_id: Some(17000),
+        transaction_type: Some(U64::from(signer::EIP1559_TX_TYPE)),
+        max_fee_per_gas: Some(U256::from(30_000_000_000u64)),
+        max_priority_fee_per_gas: Some(U256::from(1_500_000_000u64)),
+        access_list: Some(vec![AccessListItem {
+            address: Address::from_str("0x4D77a078a8f698b73b449866ec620DbDc921df39").unwrap(),
+            storage_keys: vec![H256::from_low_u64_be(1)],
+        }]),
+        ..Default::default()
+    }
+}
+
+// Lets web3's own transaction encoder sign through our signer
+struct ReferenceKey(secp256k1::SecretKey);
+
+impl Key for ReferenceKey {
+    fn sign(&self, _message: &[u8], _chain_id: Option<u64>) -> Result<Signature, SigningError> {
+        // Only typed transactions are signed in these tests, a legacy signature fails the test
+        Err(SigningError::InvalidMessage)
+    }
+
+    fn sign_message(&self, message: &[u8]) -> Result<Signature, SigningError> {
+        let hash: [u8; 32] = message.try_into().map_err(|_| SigningError::InvalidMessage)?;
+        let (rec_id, sig_bytes) = signer::sign_hash(&hash, &self.0).map_err(|_| SigningError::InvalidMessage)?;
+        Ok(Signature {
+            v: rec_id,
+            r: H256::from_slice(&sig_bytes[0..32]),
+            s: H256::from_slice(&sig_bytes[32..64]),
+        })
+    }
+
+    fn address(&self) -> Address {
+        signer::secret_key_to_address(&self.0)
+    }
+}
+
+#[test]
+fn encodes_eip1559_fields_in_order() {
+    let secret_key = signer::parse_secret_key(EIP155_PRIVATE_KEY).unwrap();
+    let tx = eip1559_transaction();
+    let raw_tx = signer::sign_transaction(&tx, 17000, &secret_key).unwrap();
+
+    assert_eq!(raw_tx[0], signer::EIP1559_TX_TYPE);
+    let rlp = Rlp::new(&raw_tx[1..]);
+    assert_eq!(rlp.item_count().unwrap(), 12);
+    assert_eq!(rlp.val_at::<u64>(0).unwrap(), 17000);
+    assert_eq!(rlp.val_at::<U256>(1).unwrap(), tx.nonce.unwrap());
+    assert_eq!(rlp.val_at::<U256>(2).unwrap(), tx.max_priority_fee_per_gas.unwrap());
+    assert_eq!(rlp.val_at::<U256>(3).unwrap(), tx.max_fee_per_gas.unwrap());
+    assert_eq!(rlp.val_at::<U256>(4).unwrap(), tx.gas);
+    assert_eq!(rlp.val_at::<Address>(5).unwrap(), tx.to.unwrap());
+    assert_eq!(rlp.val_at::<U256>(6).unwrap(), tx.value);
+    assert_eq!(rlp.val_at::<Vec<u8>>(7).unwrap(), tx.data.0);
+
+    let access_list = rlp.at(8).unwrap();
+    assert_eq!(access_list.item_count().unwrap(), 1);
+    assert_eq!(access_list.at(0).unwrap().val_at::<Address>(0).unwrap(), tx.access_list.as_ref().unwrap()[0].address);
+    assert_eq!(access_list.at(0).unwrap().list_at::<H256>(1).unwrap(), vec![H256::from_low_u64_be(1)]);
+
+    assert!(rlp.val_at::<u64>(9).unwrap() <= 1);
+}
+
+#[tokio::test]
+async fn eip1559_transaction_matches_web3_signing() {
+    let secret_key = signer::parse_secret_key(EIP155_PRIVATE_KEY).unwrap();
+    let raw_tx = signer::sign_transaction(&eip1559_transaction(), 17000, &secret_key).unwrap();
+
+    // Every field is set, so web3 encodes and hashes locally without touching the transport
+    let web3 = Web3::new(Http::new("http://127.0.0.1:1").unwrap());
+    let reference = web3.accounts()
+        .sign_transaction(eip1559_transaction(), ReferenceKey(secret_key))
+        .await
+        .unwrap();
+
+    assert_eq!(raw_tx, reference.raw_transaction.0);
+}
+
+#[test]
+fn recovers_sender_of_eip1559_transaction() {
+    let secret_key = signer::parse_secret_key(EIP155_PRIVATE_KEY).unwrap();
+    let expected = Address::from_str(EIP155_ADDRESS).unwrap();
+
+    for nonce in 0..8u64 {
+        let tx = TransactionParameters {
+            nonce: Some(U256::from(nonce)),
+            ..eip1559_transaction()
+        };
+        let raw_tx = signer::sign_transaction(&tx, 17000, &secret_key).unwrap();
+
+        let (sender, chain_id) = signer::recover_transaction_sender(&raw_tx).unwrap();
+        assert_eq!(sender, expected, "wrong sender for nonce {}", nonce);
+        assert_eq!(chain_id, 17000);
+    }
+}
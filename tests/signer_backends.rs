@@ -0,0 +1,126 @@
+mod common;
+
+use lobster::utils::signer::{self, KeystoreSigner, PrivateKeySigner, RemoteSigner, Signer};
+use secp256k1::SecretKey;
+use serde_json::{json, Value};
+use std::str::FromStr;
+use web3::types::{Address, TransactionParameters, U256, U64};
+
+// Key and password of the Web3 Secret Storage test vectors the keystore fixtures encrypt
+const KEYSTORE_PRIVATE_KEY: &str = "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d";
+const KEYSTORE_PASSWORD: &str = "testpassword";
+
+const OTHER_PRIVATE_KEY: &str = "0x4646464646464646464646464646464646464646464646464646464646464646";
+
+fn relay_transaction() -> TransactionParameters {
+    TransactionParameters {
+        nonce: Some(U256::from(7)),
+        gas: U256::from(120000),
+        to: Some(Address::from_str("0x1533600886E59FD9FC1Af1c801C38D4dD9582935").unwrap()),
+        data: hex::decode("a9059cbb").unwrap().into(),
+        transaction_type: Some(U64::from(signer::EIP1559_TX_TYPE)),
+        max_fee_per_gas: Some(U256::from(40_000_000_000u64)),
+        max_priority_fee_per_gas: Some(U256::from(2_000_000_000u64)),
+        ..Default::default()
+    }
+}
+
+// Stand-in for a remote signer: answers eth_signTransaction like Clef, signing with a local key
+async fn start_remote_signer(private_key: &str) -> String {
+    let secret_key = signer::parse_secret_key(private_key).unwrap();
+    common::start_node(move |request| Ok(sign_request(&secret_key, &request["params"][0]))).await
+}
+
+fn sign_request(secret_key: &SecretKey, request: &Value) -> Value {
+    let quantity = |field: &str| serde_json::from_value::<U256>(request[field].clone()).unwrap();
+    let tx = TransactionParameters {
+        nonce: Some(quantity("nonce")),
+        to: Some(serde_json::from_value(request["to"].clone()).unwrap()),
+        gas: quantity("gas"),
+        value: quantity("value"),
+        data: serde_json::from_value(request["data"].clone()).unwrap(),
+        transaction_type: Some(serde_json::from_value(request["type"].clone()).unwrap()),
+        max_fee_per_gas: Some(quantity("maxFeePerGas")),
+        max_priority_fee_per_gas: Some(quantity("maxPriorityFeePerGas")),
+        ..Default::default()
+    };
+    let chain_id = quantity("chainId").as_u64();
+
+    let raw_tx = signer::sign_transaction(&tx, chain_id, secret_key).unwrap();
+    json!({ "raw": format!("0x{}", hex::encode(raw_tx)), "tx": request })
+}
+
+#[tokio::test]
+async fn private_key_signer_signs_like_the_encoder() {
+    let key = PrivateKeySigner::from_hex(OTHER_PRIVATE_KEY).unwrap();
+    let secret_key = signer::parse_secret_key(OTHER_PRIVATE_KEY).unwrap();
+
+    let raw_tx = key.sign_transaction(&relay_transaction(), 17000).await.unwrap();
+    assert_eq!(raw_tx, signer::sign_transaction(&relay_transaction(), 17000, &secret_key).unwrap());
+    assert_eq!(key.address(), signer::secret_key_to_address(&secret_key));
+}
+
+#[tokio::test]
+async fn unlocks_scrypt_keystore() {
+    let keystore = KeystoreSigner::open("tests/fixtures/keystore-scrypt.json", KEYSTORE_PASSWORD).unwrap();
+    let key = PrivateKeySigner::from_hex(KEYSTORE_PRIVATE_KEY).unwrap();
+
+    assert_eq!(keystore.address(), key.address());
+    assert_eq!(
+        keystore.sign_transaction(&relay_transaction(), 11155111).await.unwrap(),
+        key.sign_transaction(&relay_transaction(), 11155111).await.unwrap()
+    );
+}
+
+#[test]
+fn unlocks_pbkdf2_keystore() {
+    let keystore = KeystoreSigner::open("tests/fixtures/keystore-pbkdf2.json", KEYSTORE_PASSWORD).unwrap();
+    let key = PrivateKeySigner::from_hex(KEYSTORE_PRIVATE_KEY).unwrap();
+    assert_eq!(keystore.address(), key.address());
+}
+
+#[test]
+fn rejects_wrong_keystore_password() {
+    assert!(KeystoreSigner::open("tests/fixtures/keystore-scrypt.json", "wrongpassword").is_err());
+}
+
+#[tokio::test]
+async fn remote_signer_returns_transaction_signed_by_the_service() {
+    let key = PrivateKeySigner::from_hex(KEYSTORE_PRIVATE_KEY).unwrap();
+    let address = key.address();
+    let expected = key.sign_transaction(&relay_transaction(), 17000).await.unwrap();
+
+    let url = start_remote_signer(KEYSTORE_PRIVATE_KEY).await;
+    let remote = RemoteSigner::new(&url, address).unwrap();
+
+    assert_eq!(remote.sign_transaction(&relay_transaction(), 17000).await.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn remote_signer_rejects_transaction_from_another_account() {
+    let expected_address = PrivateKeySigner::from_hex(KEYSTORE_PRIVATE_KEY).unwrap().address();
+    let url = start_remote_signer(OTHER_PRIVATE_KEY).await;
+    let remote = RemoteSigner::new(&url, expected_address).unwrap();
+
+    assert!(remote.sign_transaction(&relay_transaction(), 17000).await.is_err());
+}
+
+#[test]
+fn reads_signer_from_env_ignoring_empty_values() {
+    // Left empty as in .env.example
+    std::env::set_var("SIGNER_TYPE", "keystore");
+    std::env::set_var("KEYSTORE_PATH", "tests/fixtures/keystore-pbkdf2.json");
+    std::env::set_var("KEYSTORE_PASSWORD", KEYSTORE_PASSWORD);
+    std::env::set_var("KEYSTORE_PASSWORD_FILE", "");
+    let keystore = KeystoreSigner::open("tests/fixtures/keystore-pbkdf2.json", KEYSTORE_PASSWORD).unwrap();
+    assert_eq!(signer::signer_from_env().unwrap().address(), keystore.address());
+
+    std::env::set_var("KEYSTORE_PASSWORD", "");
+    let error = signer::signer_from_env().err().unwrap();
+    assert!(error.to_string().contains("KEYSTORE_PASSWORD or KEYSTORE_PASSWORD_FILE must be set"), "{}", error);
+
+    std::env::set_var("SIGNER_TYPE", "remote");
+    std::env::remove_var("REMOTE_SIGNER_URL");
+    let error = signer::signer_from_env().err().unwrap();
+    assert!(error.to_string().contains("REMOTE_SIGNER_URL must be set"), "{}", error);
+}
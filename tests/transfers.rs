@@ -0,0 +1,169 @@
+mod common;
+
+use lobster::models::bridge::{BridgeEvent, BridgeEventKind, BridgeEventStatus};
+use lobster::models::numeric::DepositNonce;
+use lobster::models::transfer::{BridgeTransfer, TransferStatus};
+use lobster::repositories::bridge as bridge_repo;
+use lobster::repositories::relay as relay_repo;
+use lobster::repositories::transfer as transfer_repo;
+use sqlx::PgPool;
+use web3::types::{Address, U256};
+
+// The view pairs events of every network by nonce, each test uses nonces no other test does
+fn nonce(test: u64) -> u64 {
+    (u64::from(std::process::id()) << 16) + (test << 8)
+}
+
+fn network(name: &str) -> String {
+    format!("{}-{}", name, std::process::id())
+}
+
+async fn save_event(pool: &PgPool, network: &str, kind: BridgeEventKind, nonce: u64, tx_hash: &str) -> i32 {
+    let mut event = BridgeEvent::new(network, kind, U256::from(nonce), Some(100), Some(tx_hash.to_string()));
+    event.block_hash = Some(format!("0x{:064x}", 100));
+    event.log_index = Some(0);
+    bridge_repo::save_bridge_event(pool, &event).await.unwrap();
+
+    sqlx::query_scalar("SELECT id FROM bridge_events WHERE network = $1 AND tx_hash = $2")
+        .bind(network)
+        .bind(tx_hash)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+async fn save_deposit(pool: &PgPool, network: &str, nonce: u64, status: BridgeEventStatus) -> i32 {
+    let kind = BridgeEventKind::Deposit {
+        token: Address::repeat_byte(0x01),
+        from: Address::repeat_byte(0x02),
+        to: Address::repeat_byte(0x03),
+        amount: U256::from(1000),
+    };
+    let id = save_event(pool, network, kind, nonce, &format!("0x{:064x}", nonce)).await;
+    bridge_repo::set_event_status(pool, id, status).await.unwrap();
+    id
+}
+
+async fn save_distribution(pool: &PgPool, network: &str, nonce: u64, tx_hash: &str) {
+    let kind = BridgeEventKind::Distribution {
+        token: Address::repeat_byte(0x04),
+        to: Address::repeat_byte(0x03),
+        amount: U256::from(1000),
+    };
+    save_event(pool, network, kind, nonce, tx_hash).await;
+}
+
+async fn transfer(pool: &PgPool, network: &str, nonce: u64) -> BridgeTransfer {
+    transfer_repo::get_transfer(pool, network, DepositNonce(U256::from(nonce))).await.unwrap().unwrap()
+}
+
+async fn delete_networks(pool: &PgPool, networks: &[&str]) {
+    for network in networks {
+        sqlx::query("DELETE FROM bridge_events WHERE network = $1")
+            .bind(network)
+            .execute(pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM relay_transactions WHERE job_id IN (SELECT id FROM relay_jobs WHERE source_network = $1)")
+            .bind(network)
+            .execute(pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM relay_jobs WHERE source_network = $1")
+            .bind(network)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn pairs_deposits_without_a_relay_job() {
+    let pool = common::connect_db().await;
+    let (source, target) = (network("pairing-source"), network("pairing-target"));
+    let nonce = nonce(1);
+
+    // A historical deposit is never relayed by us, but may have been paid out
+    save_deposit(&pool, &source, nonce, BridgeEventStatus::Historical).await;
+    save_distribution(&pool, &target, nonce, "0xd1").await;
+
+    let transfer = transfer(&pool, &source, nonce).await;
+    assert_eq!(transfer.relay_status, None);
+    assert_eq!(transfer.target_network.as_deref(), Some(target.as_str()));
+    assert_eq!(transfer.distribution_tx_hash.as_deref(), Some("0xd1"));
+    assert_eq!(transfer.status, TransferStatus::Completed);
+
+    delete_networks(&pool, &[&source, &target]).await;
+}
+
+#[tokio::test]
+async fn pairs_a_shared_nonce_only_through_the_relay_transactions() {
+    let pool = common::connect_db().await;
+    let (first, second, target) = (network("shared-first"), network("shared-second"), network("shared-target"));
+    let nonce = nonce(2);
+
+    // Deposits on two chains share the nonce of one distribution
+    save_deposit(&pool, &first, nonce, BridgeEventStatus::Confirmed).await;
+    save_deposit(&pool, &second, nonce, BridgeEventStatus::Confirmed).await;
+    save_distribution(&pool, &target, nonce, "0xd2").await;
+
+    // Nothing tells which deposit it paid out
+    assert_eq!(transfer(&pool, &first, nonce).await.distribution_tx_hash, None);
+    assert_eq!(transfer(&pool, &second, nonce).await.distribution_tx_hash, None);
+
+    // The relay job of the first deposit sent it
+    let (job_id, _) = relay_repo::claim_job(&pool, &first, DepositNonce(U256::from(nonce)), None, &target)
+        .await
+        .unwrap()
+        .unwrap();
+    let sent = "INSERT INTO relay_transactions (job_id, network, attempt, tx_hash, nonce, to_address, call_data, gas, status)
+        VALUES ($1, $2, 0, '0xd2', 0, '0x', '0x', 100000, 'Pending')";
+    sqlx::query(sent)
+        .bind(job_id)
+        .bind(&target)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let paired = transfer(&pool, &first, nonce).await;
+    assert_eq!(paired.distribution_tx_hash.as_deref(), Some("0xd2"));
+    assert_eq!(paired.status, TransferStatus::Completed);
+    assert_eq!(transfer(&pool, &second, nonce).await.distribution_tx_hash, None);
+
+    delete_networks(&pool, &[&first, &second, &target]).await;
+}
+
+#[tokio::test]
+async fn derives_failed_before_stuck_before_relayed() {
+    let pool = common::connect_db().await;
+    let (source, target) = (network("status-source"), network("status-target"));
+    let (old_failed, old_sent, recent_sent, recent) = (nonce(3), nonce(3) + 1, nonce(3) + 2, nonce(3) + 3);
+
+    for nonce in [old_failed, old_sent, recent_sent, recent] {
+        save_deposit(&pool, &source, nonce, BridgeEventStatus::Confirmed).await;
+    }
+    for nonce in [old_failed, old_sent] {
+        sqlx::query("UPDATE bridge_events SET created_at = CURRENT_TIMESTAMP - INTERVAL '1 hour' WHERE network = $1 AND nonce = $2")
+            .bind(&source)
+            .bind(DepositNonce(U256::from(nonce)))
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+
+    let claim = |nonce: u64| relay_repo::claim_job(&pool, &source, DepositNonce(U256::from(nonce)), None, &target);
+    let (job_id, _) = claim(old_failed).await.unwrap().unwrap();
+    relay_repo::mark_failed(&pool, job_id, "execution reverted").await.unwrap();
+    for nonce in [old_sent, recent_sent] {
+        let (job_id, _) = claim(nonce).await.unwrap().unwrap();
+        relay_repo::mark_sent(&pool, job_id, &format!("0x{:x}", nonce)).await.unwrap();
+    }
+
+    // A failed relay stays failed however old, an unpaid old deposit is stuck even if relayed
+    assert_eq!(transfer(&pool, &source, old_failed).await.status, TransferStatus::Failed);
+    assert_eq!(transfer(&pool, &source, old_sent).await.status, TransferStatus::Stuck);
+    assert_eq!(transfer(&pool, &source, recent_sent).await.status, TransferStatus::Relayed);
+    assert_eq!(transfer(&pool, &source, recent).await.status, TransferStatus::Pending);
+
+    delete_networks(&pool, &[&source, &target]).await;
+}